@@ -20,10 +20,13 @@ mod sqlite3;
 pub use self::sqlite3::Sqlite;
 
 #[allow(unused_imports)]
-use crate::{types::Type, Migration};
+use crate::{
+    types::{Identity, Type, WrappedDefault},
+    Migration,
+};
 
 /// An enum describing all supported Sql flavours
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum SqlVariant {
     #[cfg(feature = "sqlite3")]
     Sqlite,
@@ -54,36 +57,202 @@ impl SqlVariant {
 
 /// A generic SQL generator trait
 pub trait SqlGenerator {
+    /// The Sql flavour this generator produces
+    fn variant() -> SqlVariant;
+
+    /// The length limit of identifiers, if <name> is longer than that
+    fn identifier_limit(name: &str) -> Option<usize>;
+
+    /// The character identifiers are quoted with
+    fn identifier_quote() -> char;
+
+    /// Whether <word> is a keyword that needs quotes to be used as an identifier
+    fn is_reserved(word: &str) -> bool;
+
+    /// Whether a single `ALTER TABLE` can make several changes
+    fn combines_alterations() -> bool;
+
     /// Create a new table with a name
     fn create_table(name: &str, schema: Option<&str>) -> String;
 
     /// Create a new table with a name, only if it doesn't exist
     fn create_table_if_not_exists(name: &str, schema: Option<&str>) -> String;
 
+    /// Create a new table with the columns of the composite type <type_name>
+    fn create_typed_table(name: &str, type_name: &str, schema: Option<&str>) -> String;
+
+    /// Create a new table with the same columns as <source>
+    fn create_table_like(name: &str, source: &str, including: &[String], schema: Option<&str>) -> String;
+
+    /// Mark a newly created table as `STRICT`
+    fn strict_table() -> String;
+
+    /// Set storage parameters for a newly created table
+    fn storage_params(params: &[(String, String)]) -> String;
+
+    /// Create a view for a raw `SELECT` query, optionally replacing an existing one
+    fn create_view(name: &str, select: &str, or_replace: bool, check_option: bool, schema: Option<&str>) -> String;
+
+    /// Drop a view with a name
+    fn drop_view(name: &str, schema: Option<&str>) -> String;
+
+    /// Create a materialized view for a raw `SELECT` query
+    fn create_materialized_view(name: &str, select: &str, schema: Option<&str>) -> String;
+
+    /// Drop a materialized view with a name
+    fn drop_materialized_view(name: &str, schema: Option<&str>) -> String;
+
+    /// Refresh the contents of a materialized view
+    fn refresh_materialized_view(name: &str, concurrently: bool, schema: Option<&str>) -> String;
+
     /// Drop a table with a name
     fn drop_table(name: &str, schema: Option<&str>) -> String;
 
     /// Drop a table with a name, only if it exists
     fn drop_table_if_exists(name: &str, schema: Option<&str>) -> String;
 
+    /// Drop a table with a name, unless other objects depend on it
+    fn drop_table_restrict(name: &str, schema: Option<&str>) -> String;
+
     /// Rename a table from <old> to <new>
     fn rename_table(old: &str, new: &str, schema: Option<&str>) -> String;
 
+    /// Copy the values of <columns> from one table into another
+    fn copy_rows(from: &str, to: &str, columns: &[String], schema: Option<&str>) -> String;
+
+    /// Move a table from its current schema into <new_schema>
+    fn move_table_to_schema(name: &str, schema: Option<&str>, new_schema: &str) -> String;
+
+    /// Make <role> the owner of a table
+    fn set_table_owner(table: &str, role: &str, schema: Option<&str>) -> String;
+
+    /// Reorder the rows of a table to match <index>
+    fn cluster_table(table: &str, index: &str, schema: Option<&str>) -> String;
+
+    /// Make a table logged, or unlogged to skip the write-ahead log
+    fn set_table_logged(table: &str, logged: bool, schema: Option<&str>) -> String;
+
+    /// Change what identifies a row of a table in logical replication
+    fn set_replica_identity(table: &str, identity: &str, schema: Option<&str>) -> String;
+
+    /// Give <role> privileges on a table
+    fn grant(privileges: &[String], table: &str, role: &str, schema: Option<&str>) -> String;
+
+    /// Take privileges on a table away from <role>
+    fn revoke(privileges: &[String], table: &str, role: &str, schema: Option<&str>) -> String;
+
+    /// Create a sequence that counts from <start> in steps of <increment>
+    fn create_sequence(name: &str, start: i64, increment: i64, schema: Option<&str>) -> String;
+
+    /// Drop a sequence with a name
+    fn drop_sequence(name: &str, schema: Option<&str>) -> String;
+
+    /// Make a sequence owned by a table column
+    fn set_sequence_owner(sequence: &str, table: &str, column: &str, schema: Option<&str>) -> String;
+
+    /// Attach a table as a partition of `parent`, for the values in `bounds`
+    fn attach_partition(parent: &str, child: &str, bounds: &str, schema: Option<&str>) -> String;
+
+    /// Detach a partition from `parent`, keeping it as a regular table
+    fn detach_partition(parent: &str, child: &str, schema: Option<&str>) -> String;
+
     /// Modify a table in some other way
     fn alter_table(name: &str, schema: Option<&str>) -> String;
 
+    /// Rename a value of the named enum type
+    fn rename_enum_value(name: &str, old: &str, new: &str, schema: Option<&str>) -> String;
+
+    /// Create a schema with a name
+    fn create_schema(name: &str) -> String;
+
+    /// Create a schema with a name, only if it doesn't exist
+    fn create_schema_if_not_exists(name: &str) -> String;
+
+    /// Drop a schema, and with `cascade` everything it contains
+    fn drop_schema(name: &str, cascade: bool) -> String;
+
+    /// Attach a comment to a schema, or `None` if that isn't supported
+    fn comment_on_schema(name: &str, comment: &str) -> Option<String>;
+
+    /// Collect statistics about a table, or all tables
+    fn analyze(table: Option<&str>, schema: Option<&str>) -> String;
+
+    /// Insert rows of values into the columns of a table
+    fn insert_rows(
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<WrappedDefault<'static>>],
+        schema: Option<&str>,
+    ) -> String;
+
+    /// Change a setting for the current session
+    fn set_session(key: &str, value: &str) -> String;
+
     /// Create a new column with a type
     fn add_column(ex: bool, schema: Option<&str>, name: &str, _type: &Type) -> String;
 
+    /// Create a named table-level constraint
+    fn add_constraint(ex: bool, schema: Option<&str>, name: &str, _type: &Type) -> String;
+
+    /// Create a unique constraint without a name
+    fn add_unique(ex: bool, schema: Option<&str>, _type: &Type) -> String;
+
+    /// Declare an index inside of a `CREATE TABLE` statement, if possible
+    fn inline_index(name: &str, _type: &Type) -> Option<String>;
+
     /// Drop an existing column from the table
     fn drop_column(name: &str) -> String;
 
     /// Rename an existing column
     fn rename_column(old: &str, new: &str) -> String;
 
+    /// Create a primary key over multiple columns
+    fn primary_key(ex: bool, columns: &[String]) -> String;
+
+    /// Remove the primary key of a table
+    fn drop_primary_key(table: &str) -> String;
+
+    /// Start generating values for an existing column
+    fn add_identity(column: &str, mode: Identity) -> String;
+
+    /// Stop generating values for an identity column
+    fn drop_identity(column: &str) -> String;
+
+    /// Set the default value of a column, replacing any previous one
+    fn set_default(column: &str, default: &WrappedDefault) -> String;
+
+    /// Change the collation of a column, which keeps its type
+    fn set_collation(column: &str, collation: &str, _type: &Type, schema: Option<&str>) -> String;
+
     /// Create a multi-column index
     fn create_index(table: &str, schema: Option<&str>, name: &str, _type: &Type) -> String;
 
+    /// Create a multi-column index over the rows matching <conditions>
+    fn create_partial_index(
+        table: &str,
+        schema: Option<&str>,
+        name: &str,
+        _type: &Type,
+        conditions: &str,
+    ) -> String;
+
+    /// Set the storage mode of a column, or `None` if that isn't supported
+    fn set_column_storage(table: &str, schema: Option<&str>, column: &str, storage: &str) -> Option<String>;
+
+    /// Create a named enum type, or `None` if enums are declared inline
+    fn create_enum(name: &str, variants: &[String], schema: Option<&str>) -> Option<String>;
+
     /// Drop a multi-column index
-    fn drop_index(name: &str) -> String;
+    fn drop_index(table: &str, schema: Option<&str>, name: &str) -> String;
+
+    /// Comment on a named constraint, or `None` if that isn't supported
+    fn comment_on_constraint(
+        table: &str,
+        schema: Option<&str>,
+        name: &str,
+        comment: &str,
+    ) -> Option<String>;
+
+    /// Comment on a named index, or `None` if that isn't supported
+    fn comment_on_index(schema: Option<&str>, name: &str, comment: &str) -> Option<String>;
 }
@@ -3,8 +3,8 @@
 //! This module generates strings that are specific to MySQL
 //! databases. They should be thoroughly tested via unit testing
 
-use super::SqlGenerator;
-use crate::types::{BaseType, Type};
+use super::{SqlGenerator, SqlVariant};
+use crate::types::{BaseType, ConstraintType, Identity, IndexColumn, LengthSemantics, Type, WrappedDefault};
 
 /// A simple macro that will generate a schema prefix if it exists
 macro_rules! prefix {
@@ -15,9 +15,69 @@ macro_rules! prefix {
     };
 }
 
+/// Keywords MySQL doesn't accept as plain identifiers
+const RESERVED: &[&str] = &[
+    "ACCESSIBLE", "ADD", "ALL", "ALTER", "ANALYZE", "AND", "AS", "ASC", "ASENSITIVE", "BEFORE",
+    "BETWEEN", "BIGINT", "BINARY", "BLOB", "BOTH", "BY", "CALL", "CASCADE", "CASE", "CHANGE",
+    "CHAR", "CHARACTER", "CHECK", "COLLATE", "COLUMN", "CONDITION", "CONSTRAINT", "CONTINUE",
+    "CONVERT", "CREATE", "CROSS", "CUBE", "CUME_DIST", "CURRENT_DATE", "CURRENT_TIME",
+    "CURRENT_TIMESTAMP", "CURRENT_USER", "CURSOR", "DATABASE", "DATABASES", "DAY_HOUR",
+    "DAY_MICROSECOND", "DAY_MINUTE", "DAY_SECOND", "DEC", "DECIMAL", "DECLARE", "DEFAULT",
+    "DELAYED", "DELETE", "DENSE_RANK", "DESC", "DESCRIBE", "DETERMINISTIC", "DISTINCT",
+    "DISTINCTROW", "DIV", "DOUBLE", "DROP", "DUAL", "EACH", "ELSE", "ELSEIF", "EMPTY", "ENCLOSED",
+    "ESCAPED", "EXCEPT", "EXISTS", "EXIT", "EXPLAIN", "FALSE", "FETCH", "FIRST_VALUE", "FLOAT",
+    "FLOAT4", "FLOAT8", "FOR", "FORCE", "FOREIGN", "FROM", "FULLTEXT", "FUNCTION", "GENERATED",
+    "GET", "GRANT", "GROUP", "GROUPING", "GROUPS", "HAVING", "HIGH_PRIORITY", "HOUR_MICROSECOND",
+    "HOUR_MINUTE", "HOUR_SECOND", "IF", "IGNORE", "IN", "INDEX", "INFILE", "INNER", "INOUT",
+    "INSENSITIVE", "INSERT", "INT", "INT1", "INT2", "INT3", "INT4", "INT8", "INTEGER", "INTERSECT",
+    "INTERVAL", "INTO", "IO_AFTER_GTIDS", "IO_BEFORE_GTIDS", "IS", "ITERATE", "JOIN", "JSON_TABLE",
+    "KEY", "KEYS", "KILL", "LAG", "LAST_VALUE", "LATERAL", "LEAD", "LEADING", "LEAVE", "LEFT",
+    "LIKE", "LIMIT", "LINEAR", "LINES", "LOAD", "LOCALTIME", "LOCALTIMESTAMP", "LOCK", "LONG",
+    "LONGBLOB", "LONGTEXT", "LOOP", "LOW_PRIORITY", "MASTER_BIND", "MASTER_SSL_VERIFY_SERVER_CERT",
+    "MATCH", "MAXVALUE", "MEDIUMBLOB", "MEDIUMINT", "MEDIUMTEXT", "MIDDLEINT",
+    "MINUTE_MICROSECOND", "MINUTE_SECOND", "MOD", "MODIFIES", "NATURAL", "NOT",
+    "NO_WRITE_TO_BINLOG", "NTH_VALUE", "NTILE", "NULL", "NUMERIC", "OF", "ON", "OPTIMIZE",
+    "OPTIMIZER_COSTS", "OPTION", "OPTIONALLY", "OR", "ORDER", "OUT", "OUTER", "OUTFILE", "OVER",
+    "PARTITION", "PERCENT_RANK", "PRECISION", "PRIMARY", "PROCEDURE", "PURGE", "RANGE", "RANK",
+    "READ", "READS", "READ_WRITE", "REAL", "RECURSIVE", "REFERENCES", "REGEXP", "RELEASE",
+    "RENAME", "REPEAT", "REPLACE", "REQUIRE", "RESIGNAL", "RESTRICT", "RETURN", "REVOKE", "RIGHT",
+    "RLIKE", "ROW", "ROWS", "ROW_NUMBER", "SCHEMA", "SCHEMAS", "SECOND_MICROSECOND", "SELECT",
+    "SENSITIVE", "SEPARATOR", "SET", "SHOW", "SIGNAL", "SMALLINT", "SPATIAL", "SPECIFIC", "SQL",
+    "SQLEXCEPTION", "SQLSTATE", "SQLWARNING", "SQL_BIG_RESULT", "SQL_CALC_FOUND_ROWS",
+    "SQL_SMALL_RESULT", "SSL", "STARTING", "STORED", "STRAIGHT_JOIN", "SYSTEM", "TABLE",
+    "TERMINATED", "THEN", "TINYBLOB", "TINYINT", "TINYTEXT", "TO", "TRAILING", "TRIGGER", "TRUE",
+    "UNDO", "UNION", "UNIQUE", "UNLOCK", "UNSIGNED", "UPDATE", "USAGE", "USE", "USING", "UTC_DATE",
+    "UTC_TIME", "UTC_TIMESTAMP", "VALUES", "VARBINARY", "VARCHAR", "VARCHARACTER", "VARYING",
+    "VIRTUAL", "WHEN", "WHERE", "WHILE", "WINDOW", "WITH", "WRITE", "XOR", "YEAR_MONTH",
+    "ZEROFILL",
+];
+
 /// MySQL generator backend
 pub struct MySql;
 impl SqlGenerator for MySql {
+    fn variant() -> SqlVariant {
+        SqlVariant::Mysql
+    }
+
+    fn identifier_limit(name: &str) -> Option<usize> {
+        match name.chars().count() > 64 {
+            true => Some(64),
+            false => None,
+        }
+    }
+
+    fn identifier_quote() -> char {
+        '`'
+    }
+
+    fn combines_alterations() -> bool {
+        true
+    }
+
+    fn is_reserved(word: &str) -> bool {
+        RESERVED.binary_search(&word.to_uppercase().as_str()).is_ok()
+    }
+
     fn create_table(name: &str, schema: Option<&str>) -> String {
         format!("CREATE TABLE {}`{}`", prefix!(schema), name)
     }
@@ -26,6 +86,58 @@ impl SqlGenerator for MySql {
         format!("CREATE TABLE {}`{}` IF NOT EXISTS", prefix!(schema), name)
     }
 
+    /// MySQL has no composite types to base a table on, so this panics
+    fn create_typed_table(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support typed tables!")
+    }
+
+    /// MySQL always copies the column attributes and indices
+    fn create_table_like(name: &str, source: &str, _: &[String], schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        format!("CREATE TABLE {}`{}` LIKE {}`{}`", schema, name, schema, source)
+    }
+
+    fn strict_table() -> String {
+        panic!("MySQL does not support STRICT tables!")
+    }
+
+    fn storage_params(_: &[(String, String)]) -> String {
+        panic!("MySQL does not support storage parameters!")
+    }
+
+    fn create_view(name: &str, select: &str, or_replace: bool, check_option: bool, schema: Option<&str>) -> String {
+        format!(
+            "CREATE{} VIEW {}`{}` AS {}{}",
+            match or_replace {
+                true => " OR REPLACE",
+                false => "",
+            },
+            prefix!(schema),
+            name,
+            select,
+            match check_option {
+                true => " WITH CHECK OPTION",
+                false => "",
+            }
+        )
+    }
+
+    fn drop_view(name: &str, schema: Option<&str>) -> String {
+        format!("DROP VIEW {}`{}`", prefix!(schema), name)
+    }
+
+    fn create_materialized_view(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support materialized views!")
+    }
+
+    fn drop_materialized_view(_: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support materialized views!")
+    }
+
+    fn refresh_materialized_view(_: &str, _: bool, _: Option<&str>) -> String {
+        panic!("MySQL does not support materialized views!")
+    }
+
     fn drop_table(name: &str, schema: Option<&str>) -> String {
         format!("DROP TABLE {}`{}`", prefix!(schema), name)
     }
@@ -34,15 +146,150 @@ impl SqlGenerator for MySql {
         format!("DROP TABLE {}`{}` IF EXISTS", prefix!(schema), name)
     }
 
+    /// MySQL accepts `RESTRICT` but ignores it, so it's left out
+    fn drop_table_restrict(name: &str, schema: Option<&str>) -> String {
+        MySql::drop_table(name, schema)
+    }
+
     fn rename_table(old: &str, new: &str, schema: Option<&str>) -> String {
         let schema = prefix!(schema);
         format!("RENAME TABLE {}`{}` TO {}`{}`", schema, old, schema, new)
     }
 
+    /// Both tables are in the migration schema
+    fn copy_rows(from: &str, to: &str, columns: &[String], schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        let columns = MySql::quote_all(columns);
+        format!(
+            "INSERT INTO {}`{}` ({}) SELECT {} FROM {}`{}`",
+            schema, to, columns, columns, schema, from
+        )
+    }
+
+    /// MySQL schemas are databases, so moving is a cross-database rename
+    fn move_table_to_schema(name: &str, schema: Option<&str>, new_schema: &str) -> String {
+        format!(
+            "RENAME TABLE {}`{}` TO `{}`.`{}`",
+            prefix!(schema),
+            name,
+            new_schema,
+            name
+        )
+    }
+
+    fn set_table_owner(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support table owners!")
+    }
+
+    fn cluster_table(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support clustering tables!")
+    }
+
+    fn set_table_logged(_: &str, _: bool, _: Option<&str>) -> String {
+        panic!("MySQL does not support unlogged tables!")
+    }
+
+    fn set_replica_identity(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support replica identities!")
+    }
+
+    /// MySQL account and role names are strings, not identifiers
+    fn grant(privileges: &[String], table: &str, role: &str, schema: Option<&str>) -> String {
+        format!(
+            "GRANT {} ON {}`{}` TO '{}'",
+            privileges.join(", "),
+            prefix!(schema),
+            table,
+            role
+        )
+    }
+
+    fn revoke(privileges: &[String], table: &str, role: &str, schema: Option<&str>) -> String {
+        format!(
+            "REVOKE {} ON {}`{}` FROM '{}'",
+            privileges.join(", "),
+            prefix!(schema),
+            table,
+            role
+        )
+    }
+
+    fn create_sequence(_: &str, _: i64, _: i64, _: Option<&str>) -> String {
+        panic!("MySQL does not support sequences!")
+    }
+
+    fn drop_sequence(_: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support sequences!")
+    }
+
+    fn set_sequence_owner(_: &str, _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support sequences!")
+    }
+
+    fn attach_partition(_: &str, _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support attaching tables as partitions!")
+    }
+
+    fn detach_partition(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL does not support detaching partitions!")
+    }
+
+    fn rename_enum_value(_: &str, _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("MySQL enums are part of their column, change the column instead!")
+    }
+
     fn alter_table(name: &str, schema: Option<&str>) -> String {
         format!("ALTER TABLE {}`{}`", prefix!(schema), name)
     }
 
+    /// MySQL schemas are databases, which are always dropped with their tables
+    fn create_schema(name: &str) -> String {
+        format!("CREATE DATABASE `{}`", name)
+    }
+
+    fn create_schema_if_not_exists(name: &str) -> String {
+        format!("CREATE DATABASE IF NOT EXISTS `{}`", name)
+    }
+
+    fn drop_schema(name: &str, cascade: bool) -> String {
+        match cascade {
+            true => format!("DROP DATABASE `{}`", name),
+            false => panic!("MySQL always drops the contents of a schema, use `cascade`!"),
+        }
+    }
+
+    fn analyze(table: Option<&str>, schema: Option<&str>) -> String {
+        match table {
+            Some(t) => format!("ANALYZE TABLE {}`{}`", prefix!(schema), t),
+            None => panic!("MySQL can only analyze specific tables!"),
+        }
+    }
+
+    fn insert_rows(
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<WrappedDefault<'static>>],
+        schema: Option<&str>,
+    ) -> String {
+        format!(
+            "INSERT INTO {}`{}` ({}) VALUES {}",
+            prefix!(schema),
+            table,
+            MySql::quote_all(columns),
+            rows.iter()
+                .map(|row| {
+                    let values: Vec<_> = row.iter().map(MySql::print_default).collect();
+                    format!("({})", values.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn set_session(key: &str, value: &str) -> String {
+        format!("SET {} = {}", key, value)
+    }
+
     fn add_column(ex: bool, schema: Option<&str>, name: &str, tt: &Type) -> String {
         let bt: BaseType = tt.get_inner();
         use self::BaseType::*;
@@ -50,36 +297,80 @@ impl SqlGenerator for MySql {
 
         #[cfg_attr(rustfmt, rustfmt_skip)] /* This shouldn't be formatted. It's too long */
         format!(
-            "{}{}{}{}{}",
+            "{}{}{}{}{}{}{}{}{}{}{}",
             match bt {
                 Text => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Varchar(_) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Primary => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Integer => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                SizedInteger(_) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                Decimal(_, _) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Float => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Double => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
-                UUID => unimplemented!(),
+                // MySQL has no UUID type, so they are stored as text
+                UUID => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Json => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Boolean => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Date => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                DateTime => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                DateTimeTz => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                Time => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                PgLsn => panic!("`PgLsn` is only supported by Postgres!"),
+                TsQuery => panic!("`TsQuery` is only supported by Postgres!"),
+                TsVector => panic!("`TsVector` is only supported by Postgres!"),
                 Binary => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
-                Foreign(_, _, _) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                Geometry(_) => match tt.srid {
+                    Some(srid) => format!("{}{} {} SRID {}", MySql::prefix(ex), name, MySql::print_type(bt, schema), srid),
+                    None => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                },
+                Foreign(_, _, _) => match &tt.on_delete {
+                    Some(action) => format!("{}{} {} ON DELETE {}", MySql::prefix(ex), name, MySql::print_type(bt, schema), action.keyword()),
+                    None => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
+                },
+                Enum(_, _) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Custom(_) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(bt, schema)),
                 Array(it) => format!("{}{} {}", MySql::prefix(ex), name, MySql::print_type(Array(Box::new(*it)), schema)),
                 Index(_) => unreachable!(),
+                Constraint(_, _) => unreachable!(), // Constraints are handled via custom builders
+            },
+            match tt.length_semantics {
+                Some(LengthSemantics::Char) => " CHARACTER SET utf8mb4",
+                Some(LengthSemantics::Byte) => " CHARACTER SET latin1",
+                None => "",
+            },
+            match tt.collation {
+                Some(ref c) => format!(" COLLATE {}", c),
+                None => String::new(),
+            },
+            match tt.generated {
+                Some(ref expr) => format!(" GENERATED ALWAYS AS ({}) STORED", expr),
+                None => String::new(),
             },
             match tt.primary {
                 true => " PRIMARY KEY",
                 false => "",
             },
-            match (&tt.default).as_ref() {
-                Some(ref m) => format!(" DEFAULT '{}'", m),
+            match tt.default {
+                Some(ref m) => format!(" DEFAULT {}", MySql::print_default(m)),
                 _ => format!(""),
             },
+            match tt.on_update {
+                Some(ref m) => format!(" ON UPDATE {}", MySql::print_default(m)),
+                _ => String::new(),
+            },
             match tt.nullable {
                 true => "",
                 false => " NOT NULL",
             },
+            match tt.invisible {
+                true => " INVISIBLE",
+                false => "",
+            },
+            match tt.identity {
+                Some(Identity::ByDefault) => " AUTO_INCREMENT",
+                Some(Identity::Always) => panic!("MySQL can't stop inserts from setting an AUTO_INCREMENT column!"),
+                None => "",
+            },
             match tt.unique {
                 true => " UNIQUE",
                 false => "",
@@ -87,10 +378,76 @@ impl SqlGenerator for MySql {
         )
     }
 
+    fn add_constraint(ex: bool, schema: Option<&str>, name: &str, _type: &Type) -> String {
+        match _type.inner {
+            BaseType::Constraint(ref c, ref columns) => format!(
+                "{}CONSTRAINT `{}` {}",
+                match ex {
+                    true => "ADD ",
+                    false => "",
+                },
+                name,
+                MySql::print_constraint(c, columns, schema)
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    fn add_unique(ex: bool, schema: Option<&str>, _type: &Type) -> String {
+        match _type.inner {
+            BaseType::Constraint(ref c, ref columns) => format!(
+                "{}{}",
+                match ex {
+                    true => "ADD ",
+                    false => "",
+                },
+                MySql::print_constraint(c, columns, schema)
+            ),
+            _ => unreachable!(),
+        }
+    }
+
     fn drop_column(name: &str) -> String {
         format!("DROP COLUMN `{}`", name)
     }
 
+    fn primary_key(ex: bool, columns: &[String]) -> String {
+        format!(
+            "{}PRIMARY KEY ({})",
+            match ex {
+                true => "ADD ",
+                false => "",
+            },
+            MySql::quote_all(columns)
+        )
+    }
+
+    fn drop_primary_key(_: &str) -> String {
+        String::from("DROP PRIMARY KEY")
+    }
+
+    /// MySQL can only add `AUTO_INCREMENT` by restating the whole column
+    fn add_identity(_: &str, _: Identity) -> String {
+        panic!("MySQL needs the full column definition to add AUTO_INCREMENT, use `inject_custom` with `MODIFY` instead!")
+    }
+
+    /// MySQL can only remove `AUTO_INCREMENT` by restating the whole column
+    fn drop_identity(_: &str) -> String {
+        panic!("MySQL needs the full column definition to drop AUTO_INCREMENT, use `inject_custom` with `MODIFY` instead!")
+    }
+
+    fn set_default(column: &str, default: &WrappedDefault) -> String {
+        format!("ALTER COLUMN `{}` SET DEFAULT {}", column, MySql::print_default(default))
+    }
+
+    /// MySQL restates the whole column, so `_type` needs all of its options
+    fn set_collation(column: &str, collation: &str, _type: &Type, schema: Option<&str>) -> String {
+        format!(
+            "MODIFY COLUMN {}",
+            MySql::add_column(false, schema, column, &_type.clone().collation(collation))
+        )
+    }
+
     fn rename_column(old: &str, new: &str) -> String {
         format!("CHANGE COLUMN `{}` `{}`", old, new)
     }
@@ -98,31 +455,83 @@ impl SqlGenerator for MySql {
     fn create_index(table: &str, schema: Option<&str>, name: &str, _type: &Type) -> String {
         // FIXME: Implement Mysql specific index builder here
         format!(
-            "CREATE {} INDEX `{}` ON {}`{}` ({})",
+            "CREATE {}INDEX `{}` ON {}`{}` ({})",
             match _type.unique {
-                true => "UNIQUE",
+                true => "UNIQUE ",
                 false => "",
             },
             name,
             prefix!(schema),
             table,
             match _type.inner {
-                BaseType::Index(ref cols) => cols
-                    .iter()
-                    .map(|col| format!("`{}`", col))
-                    .collect::<Vec<_>>()
-                    .join(", "),
+                BaseType::Index(ref cols) => MySql::print_index_columns(cols),
                 _ => unreachable!(),
             }
         )
     }
 
-    fn drop_index(name: &str) -> String {
-        format!("DROP INDEX `{}`", name)
+    fn inline_index(name: &str, _type: &Type) -> Option<String> {
+        Some(format!(
+            "{}INDEX `{}` ({})",
+            match _type.unique {
+                true => "UNIQUE ",
+                false => "",
+            },
+            name,
+            match _type.inner {
+                BaseType::Index(ref cols) => MySql::print_index_columns(cols),
+                _ => unreachable!(),
+            }
+        ))
+    }
+
+    fn comment_on_constraint(_: &str, _: Option<&str>, _: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn comment_on_schema(_: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn comment_on_index(_: Option<&str>, _: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn create_partial_index(_: &str, _: Option<&str>, _: &str, _: &Type, _: &str) -> String {
+        panic!("MySQL does not support partial indices!")
+    }
+
+    fn set_column_storage(_: &str, _: Option<&str>, _: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn create_enum(_: &str, _: &[String], _: Option<&str>) -> Option<String> {
+        None
+    }
+
+    fn drop_index(table: &str, schema: Option<&str>, name: &str) -> String {
+        format!("DROP INDEX `{}` ON {}`{}`", name, prefix!(schema), table)
     }
 }
 
 impl MySql {
+    fn print_default(d: &WrappedDefault) -> String {
+        use self::WrappedDefault::*;
+        match *d {
+            CurrentTimestamp => String::from("CURRENT_TIMESTAMP"),
+            // Only timestamps take a bare function, anything else needs an expression
+            CurrentTime => String::from("(CURRENT_TIME)"),
+            RandomUuid => String::from("(UUID())"),
+            Expression(ref e) => format!("({})", e),
+            Integer(_) | Float(_) | Double(_) => format!("{}", d),
+            Boolean(true) => String::from("TRUE"),
+            Boolean(false) => String::from("FALSE"),
+            // Casts are Postgres syntax, the value on its own is fine
+            Cast(ref d, _) => MySql::print_default(d),
+            _ => MySql::quote_value(&d.to_string()),
+        }
+    }
+
     fn prefix(ex: bool) -> String {
         match ex {
             true => format!("ADD COLUMN "),
@@ -141,22 +550,106 @@ impl MySql {
             /* "NOT NULL" is added here because normally primary keys are implicitly not-null */
             Primary => format!("INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY"),
             Integer => format!("INTEGER"),
+            SizedInteger(1) => String::from("TINYINT"),
+            SizedInteger(2) => String::from("SMALLINT"),
+            SizedInteger(4) => String::from("INT"),
+            SizedInteger(_) => String::from("BIGINT"),
+            Decimal(p, s) => format!("DECIMAL({}, {})", p, s),
             Float => format!("FLOAT"),
             Double => format!("DOUBLE"),
             UUID => format!("CHAR(36)"),
             Boolean => format!("BOOLEAN"),
             Date => format!("DATE"),
+            DateTime => String::from("DATETIME"),
+            DateTimeTz => String::from("TIMESTAMP"),
+            Time => String::from("TIME"),
+            PgLsn | TsQuery | TsVector => unreachable!(),
             Json => format!("JSON"),
             Binary => format!("BYTEA"),
+            Geometry(kind) => kind,
             Foreign(s, t, refs) => format!(
-                "INTEGER REFERENCES {}{}({})",
+                "INTEGER REFERENCES {}`{}`({})",
                 prefix!(s),
                 t,
-                refs.0.join(",")
+                MySql::quote_all(&refs.0)
             ),
+            Enum(_, variants) => format!("ENUM({})", MySql::quote_values(&variants)),
             Custom(t) => format!("{}", t),
             Array(meh) => format!("{}[]", MySql::print_type(*meh, schema)),
             Index(_) => unreachable!(),
+            Constraint(_, _) => unreachable!(),
+        }
+    }
+
+    /// Backslashes are escapes in MySQL strings, so they need escaping too
+    fn quote_value(value: &str) -> String {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+    }
+
+    fn quote_values(values: &[String]) -> String {
+        values
+            .iter()
+            .map(|v| MySql::quote_value(v))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_all(names: &[String]) -> String {
+        names
+            .iter()
+            .map(|n| format!("`{}`", n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// `NULL` sort order can't be chosen, MySQL always sorts them as the smallest value
+    fn print_index_columns(cols: &[IndexColumn]) -> String {
+        cols.iter()
+            .map(|col| {
+                format!(
+                    "{}{}",
+                    col.print('`'),
+                    match col.descending {
+                        true => " DESC",
+                        false => "",
+                    }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn print_constraint(c: &ConstraintType, columns: &[String], schema: Option<&str>) -> String {
+        match c {
+            ConstraintType::Unique(cols) if cols.iter().any(|c| c.expression) => {
+                panic!("MySQL can't use expressions in a UNIQUE constraint, use a unique index instead!")
+            }
+            ConstraintType::Unique(cols) => format!("UNIQUE ({})", MySql::print_index_columns(cols)),
+            ConstraintType::Foreign {
+                schema: table_schema,
+                table,
+                refs,
+                on_delete,
+                on_update,
+                index,
+            } => format!(
+                "FOREIGN KEY {}({}) REFERENCES {}`{}`({}){}{}",
+                index.as_ref().map(|i| format!("`{}` ", i)).unwrap_or_default(),
+                MySql::quote_all(columns),
+                prefix!(table_schema.as_deref().or(schema)),
+                table,
+                MySql::quote_all(refs),
+                on_delete.as_ref()
+                    .map(|a| format!(" ON DELETE {}", a.keyword()))
+                    .unwrap_or_default(),
+                on_update.as_ref()
+                    .map(|a| format!(" ON UPDATE {}", a.keyword()))
+                    .unwrap_or_default(),
+            ),
+            ConstraintType::Exclude { .. } => panic!("MySQL does not support exclusion constraints!"),
+            ConstraintType::Check { no_inherit: true, .. } => panic!("MySQL does not support table inheritance!"),
+            ConstraintType::Check { expr, .. } => format!("CHECK ({})", expr),
+            ConstraintType::JsonArray => format!("CHECK (JSON_TYPE({}) = 'ARRAY')", MySql::quote_all(columns)),
         }
     }
 }
@@ -3,8 +3,10 @@
 //! This module generates strings that are specific to Postgres
 //! databases. They should be thoroughly tested via unit testing
 
-use super::SqlGenerator;
-use crate::types::{BaseType, Type};
+use super::{SqlGenerator, SqlVariant};
+use crate::types::{
+    BaseType, ConstraintType, Identity, IndexColumn, Nulls, ReferentialAction, Type, WrappedDefault,
+};
 
 /// A simple macro that will generate a schema prefix if it exists
 macro_rules! prefix {
@@ -15,9 +17,49 @@ macro_rules! prefix {
     };
 }
 
+/// Keywords Postgres doesn't accept as plain identifiers
+const RESERVED: &[&str] = &[
+    "ALL", "ANALYSE", "ANALYZE", "AND", "ANY", "ARRAY", "AS", "ASC", "ASYMMETRIC", "AUTHORIZATION",
+    "BINARY", "BOTH", "CASE", "CAST", "CHECK", "COLLATE", "COLLATION", "COLUMN", "CONCURRENTLY",
+    "CONSTRAINT", "CREATE", "CROSS", "CURRENT_CATALOG", "CURRENT_DATE", "CURRENT_ROLE",
+    "CURRENT_SCHEMA", "CURRENT_TIME", "CURRENT_TIMESTAMP", "CURRENT_USER", "DEFAULT", "DEFERRABLE",
+    "DESC", "DISTINCT", "DO", "ELSE", "END", "EXCEPT", "FALSE", "FETCH", "FOR", "FOREIGN",
+    "FREEZE", "FROM", "FULL", "GRANT", "GROUP", "HAVING", "ILIKE", "IN", "INITIALLY", "INNER",
+    "INTERSECT", "INTO", "IS", "ISNULL", "JOIN", "LATERAL", "LEADING", "LEFT", "LIKE", "LIMIT",
+    "LOCALTIME", "LOCALTIMESTAMP", "NATURAL", "NOT", "NOTNULL", "NULL", "OFFSET", "ON", "ONLY",
+    "OR", "ORDER", "OUTER", "OVERLAPS", "PLACING", "PRIMARY", "REFERENCES", "RETURNING", "RIGHT",
+    "SELECT", "SESSION_USER", "SIMILAR", "SOME", "SYMMETRIC", "TABLE", "TABLESAMPLE", "THEN", "TO",
+    "TRAILING", "TRUE", "UNION", "UNIQUE", "USER", "USING", "VARIADIC", "VERBOSE", "WHEN", "WHERE",
+    "WINDOW", "WITH",
+];
+
 /// Postgres SQL generator backend
 pub struct Pg;
 impl SqlGenerator for Pg {
+    fn variant() -> SqlVariant {
+        SqlVariant::Pg
+    }
+
+    /// Postgres silently truncates identifiers to 63 bytes
+    fn identifier_limit(name: &str) -> Option<usize> {
+        match name.len() > 63 {
+            true => Some(63),
+            false => None,
+        }
+    }
+
+    fn identifier_quote() -> char {
+        '"'
+    }
+
+    fn combines_alterations() -> bool {
+        true
+    }
+
+    fn is_reserved(word: &str) -> bool {
+        RESERVED.binary_search(&word.to_uppercase().as_str()).is_ok()
+    }
+
     fn create_table(name: &str, schema: Option<&str>) -> String {
         format!("CREATE TABLE {}\"{}\"", prefix!(schema), name)
     }
@@ -26,6 +68,87 @@ impl SqlGenerator for Pg {
         format!("CREATE TABLE IF NOT EXISTS {}\"{}\"", prefix!(schema), name)
     }
 
+    fn create_typed_table(name: &str, type_name: &str, schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        format!("CREATE TABLE {}\"{}\" OF {}\"{}\"", schema, name, schema, type_name)
+    }
+
+    fn create_table_like(name: &str, source: &str, including: &[String], schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        format!(
+            "CREATE TABLE {}\"{}\" (LIKE {}\"{}\"{})",
+            schema,
+            name,
+            schema,
+            source,
+            including
+                .iter()
+                .map(|i| format!(" INCLUDING {}", i))
+                .collect::<String>()
+        )
+    }
+
+    fn strict_table() -> String {
+        panic!("Postgres does not support STRICT tables!")
+    }
+
+    fn storage_params(params: &[(String, String)]) -> String {
+        format!(
+            " WITH ({})",
+            params
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn create_view(name: &str, select: &str, or_replace: bool, check_option: bool, schema: Option<&str>) -> String {
+        format!(
+            "CREATE{} VIEW {}\"{}\" AS {}{}",
+            match or_replace {
+                true => " OR REPLACE",
+                false => "",
+            },
+            prefix!(schema),
+            name,
+            select,
+            match check_option {
+                true => " WITH CHECK OPTION",
+                false => "",
+            }
+        )
+    }
+
+    fn drop_view(name: &str, schema: Option<&str>) -> String {
+        format!("DROP VIEW {}\"{}\"", prefix!(schema), name)
+    }
+
+    fn create_materialized_view(name: &str, select: &str, schema: Option<&str>) -> String {
+        format!(
+            "CREATE MATERIALIZED VIEW {}\"{}\" AS {}",
+            prefix!(schema),
+            name,
+            select
+        )
+    }
+
+    fn drop_materialized_view(name: &str, schema: Option<&str>) -> String {
+        format!("DROP MATERIALIZED VIEW {}\"{}\"", prefix!(schema), name)
+    }
+
+    fn refresh_materialized_view(name: &str, concurrently: bool, schema: Option<&str>) -> String {
+        format!(
+            "REFRESH MATERIALIZED VIEW {}{}\"{}\"",
+            match concurrently {
+                true => "CONCURRENTLY ",
+                false => "",
+            },
+            prefix!(schema),
+            name
+        )
+    }
+
     fn drop_table(name: &str, schema: Option<&str>) -> String {
         format!("DROP TABLE {}\"{}\"", prefix!(schema), name)
     }
@@ -34,6 +157,10 @@ impl SqlGenerator for Pg {
         format!("DROP TABLE IF EXISTS {}\"{}\"", prefix!(schema), name)
     }
 
+    fn drop_table_restrict(name: &str, schema: Option<&str>) -> String {
+        format!("DROP TABLE {}\"{}\" RESTRICT", prefix!(schema), name)
+    }
+
     fn rename_table(old: &str, new: &str, schema: Option<&str>) -> String {
         let schema = prefix!(schema);
         format!(
@@ -42,45 +169,245 @@ impl SqlGenerator for Pg {
         )
     }
 
+    fn copy_rows(from: &str, to: &str, columns: &[String], schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        let columns = Pg::quote_all(columns);
+        format!(
+            "INSERT INTO {}\"{}\" ({}) SELECT {} FROM {}\"{}\"",
+            schema, to, columns, columns, schema, from
+        )
+    }
+
+    fn move_table_to_schema(name: &str, schema: Option<&str>, new_schema: &str) -> String {
+        format!(
+            "ALTER TABLE {}\"{}\" SET SCHEMA \"{}\"",
+            prefix!(schema),
+            name,
+            new_schema
+        )
+    }
+
+    fn set_table_owner(table: &str, role: &str, schema: Option<&str>) -> String {
+        format!("ALTER TABLE {}\"{}\" OWNER TO \"{}\"", prefix!(schema), table, role)
+    }
+
+    fn cluster_table(table: &str, index: &str, schema: Option<&str>) -> String {
+        format!("CLUSTER {}\"{}\" USING \"{}\"", prefix!(schema), table, index)
+    }
+
+    fn set_table_logged(table: &str, logged: bool, schema: Option<&str>) -> String {
+        format!(
+            "ALTER TABLE {}\"{}\" SET {}",
+            prefix!(schema),
+            table,
+            match logged {
+                true => "LOGGED",
+                false => "UNLOGGED",
+            }
+        )
+    }
+
+    fn set_replica_identity(table: &str, identity: &str, schema: Option<&str>) -> String {
+        format!("ALTER TABLE {}\"{}\" REPLICA IDENTITY {}", prefix!(schema), table, identity)
+    }
+
+    fn grant(privileges: &[String], table: &str, role: &str, schema: Option<&str>) -> String {
+        format!(
+            "GRANT {} ON {}\"{}\" TO \"{}\"",
+            privileges.join(", "),
+            prefix!(schema),
+            table,
+            role
+        )
+    }
+
+    fn revoke(privileges: &[String], table: &str, role: &str, schema: Option<&str>) -> String {
+        format!(
+            "REVOKE {} ON {}\"{}\" FROM \"{}\"",
+            privileges.join(", "),
+            prefix!(schema),
+            table,
+            role
+        )
+    }
+
+    fn create_sequence(name: &str, start: i64, increment: i64, schema: Option<&str>) -> String {
+        format!(
+            "CREATE SEQUENCE {}\"{}\" START WITH {} INCREMENT BY {}",
+            prefix!(schema),
+            name,
+            start,
+            increment
+        )
+    }
+
+    fn drop_sequence(name: &str, schema: Option<&str>) -> String {
+        format!("DROP SEQUENCE {}\"{}\"", prefix!(schema), name)
+    }
+
+    fn set_sequence_owner(sequence: &str, table: &str, column: &str, schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        format!(
+            "ALTER SEQUENCE {}\"{}\" OWNED BY {}\"{}\".\"{}\"",
+            schema, sequence, schema, table, column
+        )
+    }
+
+    fn attach_partition(parent: &str, child: &str, bounds: &str, schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        format!(
+            "ALTER TABLE {}\"{}\" ATTACH PARTITION {}\"{}\" FOR VALUES {}",
+            schema, parent, schema, child, bounds
+        )
+    }
+
+    fn detach_partition(parent: &str, child: &str, schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        format!(
+            "ALTER TABLE {}\"{}\" DETACH PARTITION {}\"{}\"",
+            schema, parent, schema, child
+        )
+    }
+
+    fn rename_enum_value(name: &str, old: &str, new: &str, schema: Option<&str>) -> String {
+        format!(
+            "ALTER TYPE {}\"{}\" RENAME VALUE '{}' TO '{}'",
+            prefix!(schema),
+            name,
+            old.replace('\'', "''"),
+            new.replace('\'', "''")
+        )
+    }
+
     fn alter_table(name: &str, schema: Option<&str>) -> String {
         format!("ALTER TABLE {}\"{}\"", prefix!(schema), name)
     }
 
+    fn create_schema(name: &str) -> String {
+        format!("CREATE SCHEMA \"{}\"", name)
+    }
+
+    fn create_schema_if_not_exists(name: &str) -> String {
+        format!("CREATE SCHEMA IF NOT EXISTS \"{}\"", name)
+    }
+
+    fn drop_schema(name: &str, cascade: bool) -> String {
+        format!(
+            "DROP SCHEMA \"{}\"{}",
+            name,
+            match cascade {
+                true => " CASCADE",
+                false => "",
+            }
+        )
+    }
+
+    fn analyze(table: Option<&str>, schema: Option<&str>) -> String {
+        match table {
+            Some(t) => format!("ANALYZE {}\"{}\"", prefix!(schema), t),
+            None => String::from("ANALYZE"),
+        }
+    }
+
+    fn insert_rows(
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<WrappedDefault<'static>>],
+        schema: Option<&str>,
+    ) -> String {
+        format!(
+            "INSERT INTO {}\"{}\" ({}) VALUES {}",
+            prefix!(schema),
+            table,
+            Pg::quote_all(columns),
+            rows.iter()
+                .map(|row| {
+                    let values: Vec<_> = row.iter().map(Pg::print_default).collect();
+                    format!("({})", values.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn set_session(key: &str, value: &str) -> String {
+        format!("SET {} = {}", key, value)
+    }
+
     fn add_column(ex: bool, schema: Option<&str>, name: &str, tt: &Type) -> String {
         let bt: BaseType = tt.get_inner();
         use self::BaseType::*;
 
         #[cfg_attr(rustfmt, rustfmt_skip)] /* This shouldn't be formatted. It's too long */
         format!(
-            "{}{}{}{}{}",
+            "{}{}{}{}{}{}{}{}{}",
             match bt {
                 Text => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Varchar(_) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Primary => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Integer => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                SizedInteger(_) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                Decimal(_, _) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Float => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Double => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 UUID => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Json => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Boolean => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Date => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                DateTime => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                DateTimeTz => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                Time => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                PgLsn => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                TsQuery => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                TsVector => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Binary => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
-                Foreign(_, _, _) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                Geometry(ref kind) => match tt.srid {
+                    Some(srid) => format!("{}\"{}\" GEOMETRY({}, {})", Pg::prefix(ex), name, kind, srid),
+                    None => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                },
+                Foreign(_, _, _) => match &tt.on_delete {
+                    Some(action) => format!("{}\"{}\" {} ON DELETE {}", Pg::prefix(ex), name, Pg::print_type(bt, schema), action.keyword()),
+                    None => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
+                },
+                Enum(_, _) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Custom(_) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(bt, schema)),
                 Array(it) => format!("{}\"{}\" {}", Pg::prefix(ex), name, Pg::print_type(Array(Box::new(*it)), schema)),
                 Index(_) => unreachable!(), // Indices are handled via custom builder
+                Constraint(_, _) => unreachable!(), // Constraints are handled via custom builders
+            },
+            match tt.compression {
+                Some(ref c) => format!(" COMPRESSION {}", c),
+                None => String::new(),
+            },
+            match tt.collation {
+                Some(ref c) => format!(" COLLATE \"{}\"", c),
+                None => String::new(),
+            },
+            match tt.identity {
+                Some(ref i) => format!(" GENERATED {} AS IDENTITY", i.keyword()),
+                None => String::new(),
+            },
+            match tt.generated {
+                Some(ref expr) => format!(" GENERATED ALWAYS AS ({}) STORED", expr),
+                None => String::new(),
             },
             match tt.primary {
                 true => " PRIMARY KEY",
                 false => "",
             },
-            match (&tt.default).as_ref() {
-                Some(ref m) => format!(" DEFAULT '{}'", m),
+            match tt.default {
+                // Enum values need to be cast to their type, unless that was done already
+                Some(ref m) if matches!(tt.inner, Enum(_, _)) && !matches!(m, WrappedDefault::Cast(_, _)) => {
+                    let cast = m.clone().cast(Pg::print_type(tt.get_inner(), schema));
+                    format!(" DEFAULT {}", Pg::print_default(&cast))
+                }
+                Some(ref m) => format!(" DEFAULT {}", Pg::print_default(m)),
                 _ => format!(""),
             },
-            match tt.nullable {
-                true => "",
-                false => " NOT NULL",
+            match (tt.nullable, &tt.not_null_name) {
+                (true, _) => String::new(),
+                (false, Some(n)) => format!(" CONSTRAINT \"{}\" NOT NULL", n),
+                (false, None) => String::from(" NOT NULL"),
             },
             match tt.unique {
                 true => " UNIQUE",
@@ -89,42 +416,190 @@ impl SqlGenerator for Pg {
         )
     }
 
+    fn add_constraint(ex: bool, schema: Option<&str>, name: &str, _type: &Type) -> String {
+        match _type.inner {
+            BaseType::Constraint(ref c, ref columns) => format!(
+                "{}CONSTRAINT \"{}\" {}",
+                match ex {
+                    true => "ADD ",
+                    false => "",
+                },
+                name,
+                Pg::print_constraint(c, columns, schema)
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    fn inline_index(_: &str, _: &Type) -> Option<String> {
+        None
+    }
+
+    fn add_unique(ex: bool, schema: Option<&str>, _type: &Type) -> String {
+        match _type.inner {
+            BaseType::Constraint(ref c, ref columns) => format!(
+                "{}{}",
+                match ex {
+                    true => "ADD ",
+                    false => "",
+                },
+                Pg::print_constraint(c, columns, schema)
+            ),
+            _ => unreachable!(),
+        }
+    }
+
     fn drop_column(name: &str) -> String {
         format!("DROP COLUMN \"{}\"", name)
     }
 
+    /// Postgres needs the constraint name, so this assumes the default `<table>_pkey`
+    fn primary_key(ex: bool, columns: &[String]) -> String {
+        format!(
+            "{}PRIMARY KEY ({})",
+            match ex {
+                true => "ADD ",
+                false => "",
+            },
+            Pg::quote_all(columns)
+        )
+    }
+
+    fn drop_primary_key(table: &str) -> String {
+        format!("DROP CONSTRAINT \"{}_pkey\"", table)
+    }
+
+    fn add_identity(column: &str, mode: Identity) -> String {
+        format!("ALTER COLUMN \"{}\" ADD GENERATED {} AS IDENTITY", column, mode.keyword())
+    }
+
+    fn drop_identity(column: &str) -> String {
+        format!("ALTER COLUMN \"{}\" DROP IDENTITY", column)
+    }
+
+    fn set_default(column: &str, default: &WrappedDefault) -> String {
+        format!("ALTER COLUMN \"{}\" SET DEFAULT {}", column, Pg::print_default(default))
+    }
+
+    fn set_collation(column: &str, collation: &str, _type: &Type, schema: Option<&str>) -> String {
+        format!(
+            "ALTER COLUMN \"{}\" TYPE {} COLLATE \"{}\"",
+            column,
+            Pg::print_type(_type.get_inner(), schema),
+            collation
+        )
+    }
+
     fn rename_column(old: &str, new: &str) -> String {
         format!("ALTER COLUMN \"{}\" RENAME TO \"{}\"", old, new)
     }
 
     fn create_index(table: &str, schema: Option<&str>, name: &str, _type: &Type) -> String {
         // FIXME: Implement PG specific index builder here
+        // Postgres always creates an index in the schema of its table,
+        // so only the table name is qualified here
         format!(
-            "CREATE {} INDEX \"{}\" ON {}\"{}\" ({})",
+            "CREATE {}INDEX \"{}\" ON {}\"{}\" {}({})",
             match _type.unique {
-                true => "UNIQUE",
+                true => "UNIQUE ",
                 false => "",
             },
             name,
             prefix!(schema),
             table,
+            match _type.index_method {
+                Some(ref method) => format!("USING {} ", method),
+                None => String::new(),
+            },
             match _type.inner {
-                BaseType::Index(ref cols) => cols
-                    .iter()
-                    .map(|col| format!("\"{}\"", col))
-                    .collect::<Vec<_>>()
-                    .join(", "),
+                BaseType::Index(ref cols) => Pg::print_index_columns(cols),
                 _ => unreachable!(),
             }
         )
     }
 
-    fn drop_index(name: &str) -> String {
-        format!("DROP INDEX \"{}\"", name)
+    fn comment_on_constraint(
+        table: &str,
+        schema: Option<&str>,
+        name: &str,
+        comment: &str,
+    ) -> Option<String> {
+        Some(format!(
+            "COMMENT ON CONSTRAINT \"{}\" ON {}\"{}\" IS '{}'",
+            name,
+            prefix!(schema),
+            table,
+            comment.replace('\'', "''")
+        ))
+    }
+
+    /// Schemas aren't qualified, so the migration schema is not used
+    fn comment_on_schema(name: &str, comment: &str) -> Option<String> {
+        Some(format!("COMMENT ON SCHEMA \"{}\" IS '{}'", name, comment.replace('\'', "''")))
+    }
+
+    /// Indices live in the schema of their table
+    fn comment_on_index(schema: Option<&str>, name: &str, comment: &str) -> Option<String> {
+        Some(format!(
+            "COMMENT ON INDEX {}\"{}\" IS '{}'",
+            prefix!(schema),
+            name,
+            comment.replace('\'', "''")
+        ))
+    }
+
+    fn create_partial_index(
+        table: &str,
+        schema: Option<&str>,
+        name: &str,
+        _type: &Type,
+        conditions: &str,
+    ) -> String {
+        format!(
+            "{} WHERE {}",
+            Pg::create_index(table, schema, name, _type),
+            conditions
+        )
+    }
+
+    fn set_column_storage(table: &str, schema: Option<&str>, column: &str, storage: &str) -> Option<String> {
+        Some(format!(
+            "ALTER TABLE {}\"{}\" ALTER COLUMN \"{}\" SET STORAGE {}",
+            prefix!(schema),
+            table,
+            column,
+            storage
+        ))
+    }
+
+    fn create_enum(name: &str, variants: &[String], schema: Option<&str>) -> Option<String> {
+        Some(format!(
+            "CREATE TYPE {}\"{}\" AS ENUM ({})",
+            prefix!(schema),
+            name,
+            Pg::quote_values(variants)
+        ))
+    }
+
+    fn drop_index(_: &str, schema: Option<&str>, name: &str) -> String {
+        format!("DROP INDEX {}\"{}\"", prefix!(schema), name)
     }
 }
 
 impl Pg {
+    fn print_default(d: &WrappedDefault) -> String {
+        use self::WrappedDefault::*;
+        match *d {
+            CurrentTimestamp => String::from("CURRENT_TIMESTAMP"),
+            CurrentTime => String::from("CURRENT_TIME"),
+            RandomUuid => String::from("gen_random_uuid()"),
+            Expression(ref e) => e.clone(),
+            Integer(_) | Float(_) | Double(_) => format!("{}", d),
+            Cast(ref d, ref t) => format!("{}::{}", Pg::print_default(d), t),
+            _ => format!("'{}'", d.to_string().replace('\'', "''")),
+        }
+    }
+
     fn prefix(ex: bool) -> String {
         match ex {
             true => format!("ADD COLUMN "),
@@ -143,22 +618,135 @@ impl Pg {
             /* "NOT NULL" is added here because normally primary keys are implicitly not-null */
             Primary => format!("SERIAL PRIMARY KEY NOT NULL"),
             Integer => format!("INTEGER"),
+            SizedInteger(1) | SizedInteger(2) => String::from("SMALLINT"),
+            SizedInteger(4) => String::from("INTEGER"),
+            SizedInteger(_) => String::from("BIGINT"),
+            Decimal(p, s) => format!("NUMERIC({}, {})", p, s),
             Float => format!("FLOAT"),
             Double => format!("DOUBLE PRECISION"),
             UUID => format!("UUID"),
             Boolean => format!("BOOLEAN"),
             Date => format!("DATE"),
+            DateTime => String::from("TIMESTAMP"),
+            DateTimeTz => String::from("TIMESTAMPTZ"),
+            Time => String::from("TIME"),
+            PgLsn => String::from("PG_LSN"),
+            TsQuery => String::from("TSQUERY"),
+            TsVector => String::from("TSVECTOR"),
             Json => format!("JSON"),
             Binary => format!("BYTEA"),
+            Geometry(kind) => match kind.as_str() {
+                "GEOMETRY" => kind,
+                _ => format!("GEOMETRY({})", kind),
+            },
             Foreign(s, t, refs) => format!(
                 "INTEGER REFERENCES {}\"{}\"({})",
                 prefix!(s.or(schema.map(|s| s.into()))),
                 t,
                 refs.0.join(",")
             ),
+            Enum(name, _) => format!("{}\"{}\"", prefix!(schema), name),
             Custom(t) => format!("{}", t),
             Array(meh) => format!("{}[]", Pg::print_type(*meh, schema)),
             Index(_) => unreachable!(), // Indices are handled via custom builder
+            Constraint(_, _) => unreachable!(), // Constraints are handled via custom builders
+        }
+    }
+
+    fn quote_values(values: &[String]) -> String {
+        values
+            .iter()
+            .map(|v| format!("'{}'", v.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_all<S: AsRef<str>>(names: &[S]) -> String {
+        names
+            .iter()
+            .map(|n| format!("\"{}\"", n.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn print_index_columns(cols: &[IndexColumn]) -> String {
+        cols.iter()
+            .map(|col| {
+                format!(
+                    "{}{}{}",
+                    col.print('"'),
+                    match col.descending {
+                        true => " DESC",
+                        false => "",
+                    },
+                    match col.nulls {
+                        Some(Nulls::First) => " NULLS FIRST",
+                        Some(Nulls::Last) => " NULLS LAST",
+                        None => "",
+                    }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn print_constraint(c: &ConstraintType, columns: &[String], schema: Option<&str>) -> String {
+        match c {
+            ConstraintType::Unique(cols) => {
+                if cols.iter().any(|c| c.is_ordered()) {
+                    panic!("Postgres can't order the columns of a UNIQUE constraint, use a unique index instead!")
+                }
+                if cols.iter().any(|c| c.expression) {
+                    panic!("Postgres can't use expressions in a UNIQUE constraint, use a unique index instead!")
+                }
+                format!("UNIQUE ({})", Pg::quote_all(columns))
+            }
+            ConstraintType::Foreign {
+                schema: table_schema,
+                table,
+                refs,
+                on_delete,
+                on_update,
+                ..
+            } => format!(
+                "FOREIGN KEY ({}) REFERENCES {}\"{}\"({}){}{}",
+                Pg::quote_all(columns),
+                prefix!(table_schema.as_deref().or(schema)),
+                table,
+                Pg::quote_all(refs),
+                on_delete.as_ref()
+                    .map(|a| match a {
+                        ReferentialAction::SetNullColumns(cols) => {
+                            format!(" ON DELETE SET NULL ({})", Pg::quote_all(cols))
+                        }
+                        _ => format!(" ON DELETE {}", a.keyword()),
+                    })
+                    .unwrap_or_default(),
+                on_update.as_ref()
+                    .map(|a| match a {
+                        ReferentialAction::SetNullColumns(_) => {
+                            panic!("Postgres can only set specific columns to NULL on delete!")
+                        }
+                        _ => format!(" ON UPDATE {}", a.keyword()),
+                    })
+                    .unwrap_or_default(),
+            ),
+            ConstraintType::Exclude { method, operators } => format!(
+                "EXCLUDE USING {} ({})",
+                method,
+                columns
+                    .iter()
+                    .zip(operators)
+                    .map(|(c, op)| format!("\"{}\" WITH {}", c, op))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ConstraintType::Check { expr, no_inherit } => format!(
+                "CHECK ({}){}",
+                expr,
+                if *no_inherit { " NO INHERIT" } else { "" }
+            ),
+            ConstraintType::JsonArray => format!("CHECK (jsonb_typeof({}::jsonb) = 'array')", Pg::quote_all(columns)),
         }
     }
 }
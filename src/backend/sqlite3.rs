@@ -1,7 +1,7 @@
 //! Sqlite3 implementation of a generator
 
-use super::SqlGenerator;
-use crate::types::{BaseType, Type};
+use super::{SqlGenerator, SqlVariant};
+use crate::types::{BaseType, ConstraintType, Identity, IndexColumn, Type, WrappedDefault};
 
 /// A simple macro that will generate a schema prefix if it exists
 macro_rules! prefix {
@@ -12,10 +12,52 @@ macro_rules! prefix {
     };
 }
 
+/// Keywords Sqlite recommends quoting when used as identifiers
+const RESERVED: &[&str] = &[
+    "ABORT", "ACTION", "ADD", "AFTER", "ALL", "ALTER", "ALWAYS", "ANALYZE", "AND", "AS", "ASC",
+    "ATTACH", "AUTOINCREMENT", "BEFORE", "BEGIN", "BETWEEN", "BY", "CASCADE", "CASE", "CAST",
+    "CHECK", "COLLATE", "COLUMN", "COMMIT", "CONFLICT", "CONSTRAINT", "CREATE", "CROSS", "CURRENT",
+    "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP", "DATABASE", "DEFAULT", "DEFERRABLE",
+    "DEFERRED", "DELETE", "DESC", "DETACH", "DISTINCT", "DO", "DROP", "EACH", "ELSE", "END",
+    "ESCAPE", "EXCEPT", "EXCLUDE", "EXCLUSIVE", "EXISTS", "EXPLAIN", "FAIL", "FILTER", "FIRST",
+    "FOLLOWING", "FOR", "FOREIGN", "FROM", "FULL", "GENERATED", "GLOB", "GROUP", "GROUPS",
+    "HAVING", "IF", "IGNORE", "IMMEDIATE", "IN", "INDEX", "INDEXED", "INITIALLY", "INNER",
+    "INSERT", "INSTEAD", "INTERSECT", "INTO", "IS", "ISNULL", "JOIN", "KEY", "LAST", "LEFT",
+    "LIKE", "LIMIT", "MATCH", "MATERIALIZED", "NATURAL", "NO", "NOT", "NOTHING", "NOTNULL", "NULL",
+    "NULLS", "OF", "OFFSET", "ON", "OR", "ORDER", "OTHERS", "OUTER", "OVER", "PARTITION", "PLAN",
+    "PRAGMA", "PRECEDING", "PRIMARY", "QUERY", "RAISE", "RANGE", "RECURSIVE", "REFERENCES",
+    "REGEXP", "REINDEX", "RELEASE", "RENAME", "REPLACE", "RESTRICT", "RETURNING", "RIGHT",
+    "ROLLBACK", "ROW", "ROWS", "SAVEPOINT", "SELECT", "SET", "TABLE", "TEMP", "TEMPORARY", "THEN",
+    "TIES", "TO", "TRANSACTION", "TRIGGER", "UNBOUNDED", "UNION", "UNIQUE", "UPDATE", "USING",
+    "VACUUM", "VALUES", "VIEW", "VIRTUAL", "WHEN", "WHERE", "WINDOW", "WITH", "WITHOUT",
+];
+
 /// We call this struct Sqlite instead of Sqlite3 because we hope not
 /// to have to break the API further down the road
 pub struct Sqlite;
 impl SqlGenerator for Sqlite {
+    fn variant() -> SqlVariant {
+        SqlVariant::Sqlite
+    }
+
+    /// Sqlite has no length limit for identifiers
+    fn identifier_limit(_: &str) -> Option<usize> {
+        None
+    }
+
+    fn identifier_quote() -> char {
+        '"'
+    }
+
+    /// Sqlite only makes one change per `ALTER TABLE`
+    fn combines_alterations() -> bool {
+        false
+    }
+
+    fn is_reserved(word: &str) -> bool {
+        RESERVED.binary_search(&word.to_uppercase().as_str()).is_ok()
+    }
+
     fn create_table(name: &str, schema: Option<&str>) -> String {
         format!("CREATE TABLE {}\"{}\"", prefix!(schema), name)
     }
@@ -24,6 +66,46 @@ impl SqlGenerator for Sqlite {
         format!("CREATE TABLE IF NOT EXISTS {}\"{}\"", prefix!(schema), name)
     }
 
+    fn create_typed_table(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support typed tables!")
+    }
+
+    fn create_table_like(_: &str, _: &str, _: &[String], _: Option<&str>) -> String {
+        panic!("Sqlite does not support creating tables like other tables!")
+    }
+
+    fn strict_table() -> String {
+        String::from(" STRICT")
+    }
+
+    fn storage_params(_: &[(String, String)]) -> String {
+        panic!("Sqlite does not support storage parameters!")
+    }
+
+    fn create_view(name: &str, select: &str, or_replace: bool, check_option: bool, schema: Option<&str>) -> String {
+        match (or_replace, check_option) {
+            (true, _) => panic!("Sqlite does not support replacing views!"),
+            (_, true) => panic!("Sqlite views are read-only and don't support a check option!"),
+            _ => format!("CREATE VIEW {}\"{}\" AS {}", prefix!(schema), name, select),
+        }
+    }
+
+    fn drop_view(name: &str, schema: Option<&str>) -> String {
+        format!("DROP VIEW {}\"{}\"", prefix!(schema), name)
+    }
+
+    fn create_materialized_view(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support materialized views!")
+    }
+
+    fn drop_materialized_view(_: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support materialized views!")
+    }
+
+    fn refresh_materialized_view(_: &str, _: bool, _: Option<&str>) -> String {
+        panic!("Sqlite does not support materialized views!")
+    }
+
     fn drop_table(name: &str, schema: Option<&str>) -> String {
         format!("DROP TABLE {}\"{}\"", prefix!(schema), name)
     }
@@ -32,51 +114,192 @@ impl SqlGenerator for Sqlite {
         format!("DROP TABLE IF EXISTS {}\"{}\"", prefix!(schema), name)
     }
 
+    /// Sqlite has no `RESTRICT`, foreign keys already stop the drop
+    fn drop_table_restrict(name: &str, schema: Option<&str>) -> String {
+        Sqlite::drop_table(name, schema)
+    }
+
     fn rename_table(old: &str, new: &str, schema: Option<&str>) -> String {
         let schema = prefix!(schema);
         format!("ALTER TABLE {}\"{}\" RENAME TO \"{}\"", schema, old, new)
     }
 
+    fn copy_rows(from: &str, to: &str, columns: &[String], schema: Option<&str>) -> String {
+        let schema = prefix!(schema);
+        let columns = Sqlite::quote_all(columns);
+        format!(
+            "INSERT INTO {}\"{}\" ({}) SELECT {} FROM {}\"{}\"",
+            schema, to, columns, columns, schema, from
+        )
+    }
+
+    fn move_table_to_schema(_: &str, _: Option<&str>, _: &str) -> String {
+        panic!("Sqlite does not support moving tables between schemas!")
+    }
+
+    fn set_table_owner(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support table owners!")
+    }
+
+    fn cluster_table(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support clustering tables!")
+    }
+
+    fn set_table_logged(_: &str, _: bool, _: Option<&str>) -> String {
+        panic!("Sqlite does not support unlogged tables!")
+    }
+
+    fn set_replica_identity(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support replica identities!")
+    }
+
+    fn grant(_: &[String], _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support privileges!")
+    }
+
+    fn revoke(_: &[String], _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support privileges!")
+    }
+
+    fn create_sequence(_: &str, _: i64, _: i64, _: Option<&str>) -> String {
+        panic!("Sqlite does not support sequences!")
+    }
+
+    fn drop_sequence(_: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support sequences!")
+    }
+
+    fn set_sequence_owner(_: &str, _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support sequences!")
+    }
+
+    fn attach_partition(_: &str, _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support attaching tables as partitions!")
+    }
+
+    fn detach_partition(_: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support detaching partitions!")
+    }
+
+    fn rename_enum_value(_: &str, _: &str, _: &str, _: Option<&str>) -> String {
+        panic!("Sqlite does not support enum types!")
+    }
+
     fn alter_table(name: &str, schema: Option<&str>) -> String {
         format!("ALTER TABLE {}\"{}\"", prefix!(schema), name)
     }
 
+    fn create_schema(_: &str) -> String {
+        panic!("Sqlite does not support creating schemas!")
+    }
+
+    fn create_schema_if_not_exists(_: &str) -> String {
+        panic!("Sqlite does not support creating schemas!")
+    }
+
+    fn drop_schema(_: &str, _: bool) -> String {
+        panic!("Sqlite does not support dropping schemas!")
+    }
+
+    fn analyze(table: Option<&str>, schema: Option<&str>) -> String {
+        match table {
+            Some(t) => format!("ANALYZE {}\"{}\"", prefix!(schema), t),
+            None => String::from("ANALYZE"),
+        }
+    }
+
+    fn insert_rows(
+        table: &str,
+        columns: &[String],
+        rows: &[Vec<WrappedDefault<'static>>],
+        schema: Option<&str>,
+    ) -> String {
+        format!(
+            "INSERT INTO {}\"{}\" ({}) VALUES {}",
+            prefix!(schema),
+            table,
+            Sqlite::quote_all(columns),
+            rows.iter()
+                .map(|row| {
+                    let values: Vec<_> = row.iter().map(Sqlite::print_default).collect();
+                    format!("({})", values.join(", "))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn set_session(_: &str, _: &str) -> String {
+        panic!("Sqlite does not support session settings, use `inject_custom` with a PRAGMA instead!")
+    }
+
     fn add_column(ex: bool, _: Option<&str>, name: &str, tt: &Type) -> String {
         let bt: BaseType = tt.get_inner();
         use self::BaseType::*;
 
+        if ex && tt.generated.is_some() {
+            panic!("Sqlite can't add stored generated columns to an existing table!");
+        }
+
+        if tt.identity.is_some() {
+            panic!("Sqlite does not support identity columns, use `types::primary` instead!");
+        }
+
         #[cfg_attr(rustfmt, rustfmt_skip)] /* This shouldn't be formatted. It's too long */
         format!(
             // SQL base - default - nullable - unique
-            "{}{}{}{}{}",
+            "{}{}{}{}{}{}{}",
             match bt {
                 Text => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Varchar(_) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Primary => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Integer => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                SizedInteger(_) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                Decimal(_, _) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Float => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Double => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 UUID => panic!("`UUID` not supported by Sqlite3. Use `Text` instead!"),
                 Json => panic!("`Json` not supported by Sqlite3. Use `Text` instead!"),
                 Boolean => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Date => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                DateTime => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                DateTimeTz => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                Time => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                PgLsn => panic!("`PgLsn` is only supported by Postgres!"),
+                TsQuery => panic!("`TsQuery` is only supported by Postgres!"),
+                TsVector => panic!("`TsVector` is only supported by Postgres!"),
                 Binary => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
-                Foreign(_, _, _) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                Geometry(_) => panic!("Sqlite does not support geometry columns!"),
+                Foreign(_, _, _) => match &tt.on_delete {
+                    Some(action) => format!("{}\"{}\" {} ON DELETE {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt), action.keyword()),
+                    None => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
+                },
+                Enum(_, ref variants) => format!("{}\"{}\" TEXT CHECK (\"{}\" IN ({}))", Sqlite::prefix(ex), name, name, Sqlite::quote_values(variants)),
                 Custom(_) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(bt)),
                 Array(it) => format!("{}\"{}\" {}", Sqlite::prefix(ex), name, Sqlite::print_type(Array(Box::new(*it)))),
                 Index(_) => unreachable!(), // Indices are handled via custom builders
+                Constraint(_, _) => unreachable!(), // Constraints are handled via custom builders
+            },
+            match tt.collation {
+                Some(ref c) => format!(" COLLATE {}", c),
+                None => String::new(),
+            },
+            match tt.generated {
+                Some(ref expr) => format!(" GENERATED ALWAYS AS ({}) STORED", expr),
+                None => String::new(),
             },
             match tt.primary {
                 true => " PRIMARY KEY",
                 false => "",
             },
-            match (&tt.default).as_ref() {
-                Some(ref m) => format!(" DEFAULT '{}'", m),
+            match tt.default {
+                Some(ref m) => format!(" DEFAULT {}", Sqlite::print_default(m)),
                 _ => format!(""),
             },
-            match tt.nullable {
-                true => "",
-                false => " NOT NULL",
+            match (tt.nullable, &tt.not_null_name) {
+                (true, _) => String::new(),
+                (false, Some(n)) => format!(" CONSTRAINT \"{}\" NOT NULL", n),
+                (false, None) => String::from(" NOT NULL"),
             },
             match tt.unique {
                 true => " UNIQUE",
@@ -88,32 +311,122 @@ impl SqlGenerator for Sqlite {
     /// Create a multi-column index
     fn create_index(table: &str, schema: Option<&str>, name: &str, _type: &Type) -> String {
         format!(
-            "CREATE {} INDEX {}\"{}\" ON \"{}\" ({});",
+            "CREATE {}INDEX {}\"{}\" ON \"{}\" ({})",
             match _type.unique {
-                true => "UNIQUE",
+                true => "UNIQUE ",
                 false => "",
             },
             prefix!(schema),
             name,
             table,
             match _type.inner {
-                BaseType::Index(ref cols) => cols
-                    .iter()
-                    .map(|col| format!("\"{}\"", col))
-                    .collect::<Vec<_>>()
-                    .join(", "),
+                BaseType::Index(ref cols) => Sqlite::print_index_columns(cols),
                 _ => unreachable!(),
             }
         )
     }
 
+    fn comment_on_constraint(_: &str, _: Option<&str>, _: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn comment_on_schema(_: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn comment_on_index(_: Option<&str>, _: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    /// Create a multi-column index over the rows matching <conditions>
+    fn create_partial_index(
+        table: &str,
+        schema: Option<&str>,
+        name: &str,
+        _type: &Type,
+        conditions: &str,
+    ) -> String {
+        format!(
+            "{} WHERE {}",
+            Sqlite::create_index(table, schema, name, _type),
+            conditions
+        )
+    }
+
+    /// Sqlite has no column storage modes, so they are skipped
+    fn set_column_storage(_: &str, _: Option<&str>, _: &str, _: &str) -> Option<String> {
+        None
+    }
+
+    fn create_enum(_: &str, _: &[String], _: Option<&str>) -> Option<String> {
+        None
+    }
+
     /// Drop a multi-column index
-    fn drop_index(name: &str) -> String {
-        format!("DROP INDEX \"{}\"", name)
+    fn drop_index(_: &str, schema: Option<&str>, name: &str) -> String {
+        format!("DROP INDEX {}\"{}\"", prefix!(schema), name)
     }
 
-    fn drop_column(_: &str) -> String {
-        panic!("Sqlite does not support dropping columns!")
+    fn add_constraint(ex: bool, _: Option<&str>, name: &str, _type: &Type) -> String {
+        if ex {
+            panic!("Sqlite does not support adding constraints to existing tables!")
+        }
+
+        match _type.inner {
+            BaseType::Constraint(ref c, ref columns) => format!(
+                "CONSTRAINT \"{}\" {}",
+                name,
+                Sqlite::print_constraint(c, columns)
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    fn inline_index(_: &str, _: &Type) -> Option<String> {
+        None
+    }
+
+    fn add_unique(ex: bool, _: Option<&str>, _type: &Type) -> String {
+        if ex {
+            panic!("Sqlite does not support adding constraints to existing tables!")
+        }
+
+        match _type.inner {
+            BaseType::Constraint(ref c, ref columns) => Sqlite::print_constraint(c, columns),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Needs Sqlite 3.35 or later
+    fn drop_column(name: &str) -> String {
+        format!("DROP COLUMN \"{}\"", name)
+    }
+
+    fn primary_key(ex: bool, columns: &[String]) -> String {
+        if ex {
+            panic!("Sqlite does not support adding primary keys to existing tables!")
+        }
+        format!("PRIMARY KEY ({})", Sqlite::quote_all(columns))
+    }
+
+    fn drop_primary_key(_: &str) -> String {
+        panic!("Sqlite does not support dropping primary keys!")
+    }
+
+    fn set_collation(_: &str, _: &str, _: &Type, _: Option<&str>) -> String {
+        panic!("Sqlite does not support changing columns!")
+    }
+
+    fn set_default(_: &str, _: &WrappedDefault) -> String {
+        panic!("Sqlite does not support changing columns!")
+    }
+
+    fn add_identity(_: &str, _: Identity) -> String {
+        panic!("Sqlite does not support identity columns!")
+    }
+
+    fn drop_identity(_: &str) -> String {
+        panic!("Sqlite does not support identity columns!")
     }
 
     fn rename_column(_: &str, _: &str) -> String {
@@ -122,6 +435,22 @@ impl SqlGenerator for Sqlite {
 }
 
 impl Sqlite {
+    fn print_default(d: &WrappedDefault) -> String {
+        use self::WrappedDefault::*;
+        match *d {
+            CurrentTimestamp => String::from("CURRENT_TIMESTAMP"),
+            CurrentTime => String::from("CURRENT_TIME"),
+            RandomUuid => panic!("Sqlite can't generate UUIDs!"),
+            Expression(ref e) => format!("({})", e),
+            Integer(_) | Float(_) | Double(_) => format!("{}", d),
+            // Sqlite has no boolean type and stores them as integers
+            Boolean(b) => format!("{}", b as i64),
+            // Casts are Postgres syntax, the value on its own is fine
+            Cast(ref d, _) => Sqlite::print_default(d),
+            _ => format!("'{}'", d.to_string().replace('\'', "''")),
+        }
+    }
+
     fn prefix(ex: bool) -> String {
         match ex {
             true => format!("ADD COLUMN "),
@@ -129,6 +458,15 @@ impl Sqlite {
         }
     }
 
+    /// Sqlite only knows five storage classes, so every type is
+    /// mapped to a name that yields the matching affinity:
+    ///
+    /// - `TEXT`, `VARCHAR(n)`, enums → `TEXT`
+    /// - `INTEGER` of any size, primary and foreign keys → `INTEGER`
+    /// - `float` and `double` → `REAL`, its only floating point type
+    /// - `BOOLEAN`, `DECIMAL`, `DATE`, `DATETIME` (with or without a
+    ///   time zone), `TIME` → `NUMERIC`
+    /// - `BLOB` → `BLOB`
     fn print_type(t: BaseType) -> String {
         use self::BaseType::*;
         match t {
@@ -138,18 +476,90 @@ impl Sqlite {
                 _ => format!("VARCHAR({})", l),
             },
             Primary => format!("INTEGER NOT NULL PRIMARY KEY"),
-            Integer => format!("INTEGER"),
+            Integer | SizedInteger(_) => format!("INTEGER"),
+            Decimal(p, s) => format!("DECIMAL({}, {})", p, s),
             Float => format!("REAL"),
-            Double => format!("DOUBLE"),
+            Double => format!("REAL"),
             UUID => unimplemented!(),
             Boolean => format!("BOOLEAN"),
             Date => format!("DATE"),
+            DateTime | DateTimeTz => String::from("DATETIME"),
+            Time => String::from("TIME"),
+            PgLsn | TsQuery | TsVector => unreachable!(),
             Json => panic!("Json is not supported by Sqlite3"),
-            Binary => format!("BINARY"),
+            Binary => format!("BLOB"),
+            Geometry(_) => unreachable!(),
             Foreign(_, t, refs) => format!("INTEGER REFERENCES {}({})", t, refs.0.join(",")),
+            Enum(_, _) => String::from("TEXT"),
             Custom(t) => format!("{}", t),
             Array(meh) => format!("{}[]", Sqlite::print_type(*meh)),
             Index(_) => unimplemented!(),
+            Constraint(_, _) => unimplemented!(),
+        }
+    }
+
+    fn quote_values(values: &[String]) -> String {
+        values
+            .iter()
+            .map(|v| format!("'{}'", v.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn quote_all(names: &[String]) -> String {
+        names
+            .iter()
+            .map(|n| format!("\"{}\"", n))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// `NULL` sort order can't be chosen, Sqlite always sorts them as the smallest value
+    fn print_index_columns(cols: &[IndexColumn]) -> String {
+        cols.iter()
+            .map(|col| {
+                format!(
+                    "{}{}",
+                    col.print('"'),
+                    match col.descending {
+                        true => " DESC",
+                        false => "",
+                    }
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Sqlite foreign keys always point into the schema of their table
+    fn print_constraint(c: &ConstraintType, columns: &[String]) -> String {
+        match c {
+            ConstraintType::Unique(cols) if cols.iter().any(|c| c.expression) => {
+                panic!("Sqlite can't use expressions in a UNIQUE constraint, use a unique index instead!")
+            }
+            ConstraintType::Unique(cols) => format!("UNIQUE ({})", Sqlite::print_index_columns(cols)),
+            ConstraintType::Foreign {
+                table,
+                refs,
+                on_delete,
+                on_update,
+                ..
+            } => format!(
+                "FOREIGN KEY ({}) REFERENCES \"{}\"({}){}{}",
+                Sqlite::quote_all(columns),
+                table,
+                Sqlite::quote_all(refs),
+                on_delete.as_ref()
+                    .map(|a| format!(" ON DELETE {}", a.keyword()))
+                    .unwrap_or_default(),
+                on_update.as_ref()
+                    .map(|a| format!(" ON UPDATE {}", a.keyword()))
+                    .unwrap_or_default(),
+            ),
+            ConstraintType::Exclude { .. } => panic!("Sqlite does not support exclusion constraints!"),
+            ConstraintType::Check { no_inherit: true, .. } => panic!("Sqlite does not support table inheritance!"),
+            ConstraintType::Check { expr, .. } => format!("CHECK ({})", expr),
+            ConstraintType::JsonArray => format!("CHECK (json_type({}) = 'array')", Sqlite::quote_all(columns)),
         }
     }
 }
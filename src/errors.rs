@@ -0,0 +1,77 @@
+//! Errors that can be found in a migration before generating SQL
+//!
+//! Most mistakes in a migration only surface once the generated SQL
+//! is run against a database. `Migration::validate` catches some of
+//! them early, returning one of these errors.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// A problem that makes a migration produce invalid SQL
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationError {
+    /// A foreign key has a different number of local and referenced columns
+    ForeignKeyColumnMismatch {
+        table: String,
+        name: String,
+        columns: usize,
+        refs: usize,
+    },
+    /// An identifier is longer than the backend allows
+    IdentifierTooLong { name: String, max: usize },
+    /// A `TEXT` column is used in an index, which MySQL can't do without a prefix length
+    TextColumnIndexed { table: String, column: String },
+    /// A `NOT NULL` column without a default is added to an existing table
+    NotNullWithoutDefault { table: String, column: String },
+    /// The predicate of a partial index is empty or has unbalanced parentheses
+    InvalidPredicate { index: String, predicate: String },
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        use self::ValidationError::*;
+        match *self {
+            ForeignKeyColumnMismatch {
+                ref table,
+                ref name,
+                columns,
+                refs,
+            } => write!(
+                f,
+                "Foreign key `{}` on table `{}` has {} column(s) but references {}",
+                name, table, columns, refs
+            ),
+            IdentifierTooLong { ref name, max } => write!(
+                f,
+                "Identifier `{}` is longer than the database limit of {}",
+                name, max
+            ),
+            TextColumnIndexed {
+                ref table,
+                ref column,
+            } => write!(
+                f,
+                "Text column `{}` on table `{}` can't be indexed by MySQL, use a `varchar` with a length instead",
+                column, table
+            ),
+            NotNullWithoutDefault {
+                ref table,
+                ref column,
+            } => write!(
+                f,
+                "Column `{}` added to table `{}` is NOT NULL without a default, which fails if the table has rows",
+                column, table
+            ),
+            InvalidPredicate {
+                ref index,
+                ref predicate,
+            } => write!(
+                f,
+                "Partial index `{}` has an empty or unbalanced predicate `{}`",
+                index, predicate
+            ),
+        }
+    }
+}
+
+impl Error for ValidationError {}
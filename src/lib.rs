@@ -104,13 +104,19 @@ pub use integrations::*;
 
 pub mod backend;
 pub mod connectors;
+pub mod errors;
 pub mod migration;
+pub mod render;
 pub mod table;
 pub mod types;
+pub mod view;
 
 pub use backend::SqlVariant;
-pub use migration::Migration;
-pub use table::{Table, TableMeta};
+pub use errors::ValidationError;
+pub use migration::{FkStyle, Migration, Summary};
+pub use render::{KeywordCase, Quoting, RenderOptions};
+pub use table::{ColumnTemplate, Table, TableMeta};
+pub use view::ViewMeta;
 
 #[cfg(test)]
 mod tests;
@@ -123,6 +129,9 @@ pub enum TableChange {
     /// Add a column of a name and type
     AddColumn(String, types::Type),
 
+    /// Add a column, but only when generating SQL for one backend
+    AddColumnFor(SqlVariant, String, types::Type),
+
     /// Change an existing column
     ChangeColumn(String, types::Type, Rc<dyn Fn(&mut types::Type)>),
 
@@ -132,6 +141,30 @@ pub enum TableChange {
     /// Remove a column
     DropColumn(String),
 
+    /// Remove the primary key of a table
+    DropPrimaryKey,
+
+    /// Turn a plain column into an identity column
+    AddIdentity(String, types::Identity),
+
+    /// Turn an identity column into a plain column
+    DropIdentity(String),
+
+    /// Replace the default value of a column
+    SetDefault(String, types::WrappedDefault<'static>),
+
+    /// Change the collation of a column (`column`, `collation`, `type`)
+    SetCollation(String, String, types::Type),
+
+    /// Recreate the table with new column definitions, keeping its rows
+    Rebuild(Vec<(String, types::Type)>),
+
+    /// Add a named table-level constraint
+    AddConstraint(String, types::Type),
+
+    /// Add a unique constraint that is named by the database
+    AddUnique(types::Type),
+
     /// Add some custom SQL if all else fails
     CustomLine(String),
 }
@@ -145,20 +178,110 @@ pub enum DatabaseChange {
     /// Create a new table *only* if it doesn't exist yet
     CreateTableIfNotExists(Table, Rc<dyn Fn(&mut Table)>),
 
+    /// Create a new table with the columns of a composite type (`table`, `type`, `cb`)
+    CreateTypedTable(Table, String, Rc<dyn Fn(&mut Table)>),
+
+    /// Create a new table from the result of a raw `SELECT` query
+    CreateTableAs(String, String),
+
+    /// Create a new table with the structure of another (`name`, `source`, `including`)
+    CreateTableLike(String, String, Vec<String>),
+
     /// Change fields on an existing table
     ChangeTable(Table, Rc<dyn Fn(&mut Table)>),
 
     /// Rename a table
     RenameTable(String, String),
 
+    /// Move a table into another schema
+    MoveTableToSchema(String, String),
+
+    /// Change the owner of a table (`table`, `role`)
+    SetTableOwner(String, String),
+
+    /// Physically reorder a table by one of its indices (`table`, `index`)
+    ClusterTable(String, String),
+
+    /// Turn the write-ahead log of a table on or off (`table`, `logged`)
+    SetTableLogged(String, bool),
+
+    /// Change what is logged to identify replicated rows (`table`, `identity`)
+    SetReplicaIdentity(String, String),
+
+    /// Give a role privileges on a table (`privileges`, `table`, `role`)
+    Grant(Vec<String>, String, String),
+
+    /// Take privileges on a table away from a role (`privileges`, `table`, `role`)
+    Revoke(Vec<String>, String, String),
+
+    /// Create a sequence (`name`, `start`, `increment`)
+    CreateSequence(String, i64, i64),
+
+    /// Drop an existing sequence
+    DropSequence(String),
+
+    /// Tie a sequence to a column (`sequence`, `table`, `column`)
+    SetSequenceOwner(String, String, String),
+
+    /// Attach a table to a partitioned table (`parent`, `child`, `bounds`)
+    AttachPartition(String, String, String),
+
+    /// Detach a partition from its partitioned table (`parent`, `child`)
+    DetachPartition(String, String),
+
+    /// Create a view from a raw `SELECT` query
+    CreateView(view::ViewMeta),
+
+    /// Drop an existing view
+    DropView(String),
+
+    /// Create a materialized view from a raw `SELECT` query
+    CreateMaterializedView(String, String),
+
+    /// Drop an existing materialized view
+    DropMaterializedView(String),
+
+    /// Re-run the query of a materialized view, optionally `CONCURRENTLY`
+    RefreshMaterializedView(String, bool),
+
     /// Drop an existing table
     DropTable(String),
 
     /// Only drop a table if it exists
     DropTableIfExists(String),
-    
+
+    /// Drop a table, failing if other objects depend on it
+    DropTableRestrict(String),
+
+    /// Rename a value of a named enum type (`enum`, `old`, `new`)
+    RenameEnumValue(String, String, String),
+
+    /// Create a new schema
+    CreateSchema(String),
+
+    /// Create a new schema *only* if it doesn't exist yet
+    CreateSchemaIfNotExists(String),
+
+    /// Drop a schema, optionally with everything in it
+    DropSchema(String, bool),
+
+    /// Document a schema (`schema`, `comment`)
+    CommentOnSchema(String, String),
+
+    /// Change a setting for the current database session
+    SetSession(String, String),
+
+    /// Collect statistics about a table, or the whole database
+    Analyze(Option<String>),
+
+    /// Insert rows of values into a table (`table`, `columns`, `rows`)
+    InsertRows(String, Vec<String>, Vec<Vec<types::WrappedDefault<'static>>>),
+
     /// Add some custom SQL if all else fails
     CustomLine(String),
+
+    /// Add custom SQL that is rendered exactly as given
+    CustomRaw(String),
 }
 
 /// An enum set that represents operations done with and on indices
@@ -171,6 +294,14 @@ pub enum IndexChange {
         columns: types::Type, // Should always be a `Index` type
     },
 
+    /// Add a multi-column index that only covers rows matching `conditions`
+    AddPartialIndex {
+        index: String,
+        table: String,
+        columns: types::Type, // Should always be a `Index` type
+        conditions: String,
+    },
+
     /// Remove a multi-column index
     RemoveIndex(String, String),
 }
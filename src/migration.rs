@@ -11,19 +11,57 @@
 //! You can also use `Migration::exec` with your SQL connection for convenience
 //! if you're a library developer.
 
-use crate::table::{Table, TableMeta};
+use crate::errors::ValidationError;
+use crate::render::RenderOptions;
+use crate::table::{validate_identifier, Table, TableMeta};
+use crate::types::WrappedDefault;
+use crate::view::ViewMeta;
 use crate::DatabaseChange;
 
 use crate::backend::{SqlGenerator, SqlVariant};
 use crate::connectors::SqlRunner;
 
+use std::collections::HashSet;
 use std::rc::Rc;
 
+/// How single-column foreign keys are rendered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FkStyle {
+    /// As an inline `REFERENCES` clause on the column
+    Inline,
+    /// As a named table-level constraint, `<table>_<column>_fkey`
+    Constraint,
+}
+
+/// How many changes of each kind a migration makes
+///
+/// Changes that fit none of the kinds, like grants or custom SQL,
+/// aren't counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Summary {
+    /// Tables, views, sequences and schemas that are created
+    pub creates: usize,
+    /// Tables, views, sequences and schemas that are dropped
+    pub drops: usize,
+    /// Tables, sequences and enums that are changed
+    pub alters: usize,
+    /// Indices that are created or dropped with a table
+    pub indexes: usize,
+}
+
 /// Represents a schema migration on a database
 pub struct Migration {
     #[doc(hidden)]
     pub schema: Option<String>,
     #[doc(hidden)]
+    pub fk_style: FkStyle,
+    #[doc(hidden)]
+    pub inline_indices: bool,
+    #[doc(hidden)]
+    pub defer_foreign_keys: bool,
+    #[doc(hidden)]
+    pub table_prefix: Option<String>,
+    #[doc(hidden)]
     pub changes: Vec<DatabaseChange>,
 }
 
@@ -31,6 +69,10 @@ impl Migration {
     pub fn new() -> Migration {
         Migration {
             schema: None,
+            fk_style: FkStyle::Inline,
+            inline_indices: false,
+            defer_foreign_keys: false,
+            table_prefix: None,
             changes: Vec::new(),
         }
     }
@@ -43,82 +85,584 @@ impl Migration {
         }
     }
 
+    /// Choose how `types::foreign` columns are rendered
+    ///
+    /// Defaults to `FkStyle::Inline`. References with more than one
+    /// key are always rendered inline.
+    pub fn fk_style(self, fk_style: FkStyle) -> Migration {
+        Self { fk_style, ..self }
+    }
+
+    /// Declare the indices of new tables in their `CREATE TABLE`
+    ///
+    /// MySQL then creates them together with the table, as
+    /// `INDEX <name> (<columns>)`, instead of with a `CREATE INDEX`
+    /// each. Other backends can't and ignore this.
+    pub fn inline_indices(self, inline_indices: bool) -> Migration {
+        Self { inline_indices, ..self }
+    }
+
+    /// Add the foreign keys of new tables once all tables exist
+    ///
+    /// Foreign key columns and constraints are taken out of their
+    /// `CREATE TABLE` and added with an `ALTER TABLE` each after the
+    /// other changes, so tables can reference each other in any order.
+    /// Columns are turned into constraints as with `FkStyle::Constraint`.
+    /// Not supported by Sqlite, which can't add constraints later.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// let mut m = Migration::new().defer_foreign_keys(true);
+    /// m.create_table("posts", |t| {
+    ///     t.add_column("author_id", types::foreign("users", "id"));
+    /// });
+    /// m.create_table("users", |t| {
+    ///     t.add_column("id", types::primary());
+    /// });
+    /// ```
+    pub fn defer_foreign_keys(self, defer_foreign_keys: bool) -> Migration {
+        Self { defer_foreign_keys, ..self }
+    }
+
+    /// Put a prefix in front of every table name of this migration
+    ///
+    /// The prefix is added when rendering, to the tables that are
+    /// created, changed or dropped as well as the ones referenced
+    /// by foreign keys.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new().table_prefix("app_");
+    /// m.drop_table("users"); // Drops `app_users`
+    /// ```
+    pub fn table_prefix<S: Into<String>>(self, prefix: S) -> Migration {
+        Self {
+            table_prefix: Some(prefix.into()),
+            ..self
+        }
+    }
+
+    /// A copy of all changes, with the table prefix applied
+    fn prefixed_changes(&self) -> Vec<DatabaseChange> {
+        use DatabaseChange::*;
+
+        let mut changes = self.changes.clone();
+        let prefix = match self.table_prefix {
+            Some(ref p) => p.clone(),
+            None => return changes,
+        };
+        let p = |name: &mut String| name.insert_str(0, &prefix);
+
+        for change in changes.iter_mut() {
+            match change {
+                CreateTable(t, cb)
+                | CreateTableIfNotExists(t, cb)
+                | CreateTypedTable(t, _, cb)
+                | ChangeTable(t, cb) => {
+                    p(&mut t.meta.name);
+
+                    // References are only known once the user code ran
+                    let (inner, prefix) = (cb.clone(), prefix.clone());
+                    *cb = Rc::new(move |t: &mut Table| {
+                        inner(t);
+                        t.prefix_foreign_keys(&prefix);
+                    });
+                }
+                CreateTableAs(name, _)
+                | DropTable(name)
+                | DropTableIfExists(name)
+                | DropTableRestrict(name)
+                | MoveTableToSchema(name, _)
+                | SetTableOwner(name, _)
+                | SetReplicaIdentity(name, _)
+                | SetTableLogged(name, _)
+                | ClusterTable(name, _)
+                | Grant(_, name, _)
+                | Revoke(_, name, _)
+                | SetSequenceOwner(_, name, _)
+                | InsertRows(name, _, _)
+                | Analyze(Some(name)) => p(name),
+                CreateTableLike(a, b, _)
+                | RenameTable(a, b)
+                | AttachPartition(a, b, _)
+                | DetachPartition(a, b) => {
+                    p(a);
+                    p(b);
+                }
+                _ => {}
+            }
+        }
+
+        changes
+    }
+
     /// Creates the SQL for this migration for a specific backend
     ///
     /// This function copies state and does not touch the original
     /// migration layout. This allows you to call `revert` later on
     /// in the process to auto-infer the down-behaviour
     pub fn make<T: SqlGenerator>(&self) -> String {
+        self.make_with::<T>(&RenderOptions::default())
+    }
+
+    /// The same as `make` but with control over the output format
+    ///
+    /// ```rust
+    /// # use barrel::{Migration, RenderOptions};
+    /// # let mut m = Migration::new();
+    /// let options = RenderOptions::new().multiline(true);
+    /// # #[cfg(feature = "pg")]
+    /// m.make_with::<barrel::backend::Pg>(&options);
+    /// ```
+    pub fn make_with<T: SqlGenerator>(&self, options: &RenderOptions) -> String {
         use DatabaseChange::*;
 
         /* What happens in make, stays in make (sort of) */
-        let mut changes = self.changes.clone();
+        let mut changes = self.prefixed_changes();
+        // Statistics are only worth collecting once everything else ran
+        changes.sort_by_key(|c| matches!(c, Analyze(_)));
         let schema = self.schema.as_ref().map(|s| s.as_str());
 
-        changes.iter_mut().fold(String::new(), |mut sql, change| {
-            match change {
-                &mut CreateTable(ref mut t, ref mut cb)
-                | &mut CreateTableIfNotExists(ref mut t, ref mut cb) => {
-                    cb(t); // Run the user code
-                    let (cols, indices) = t.make::<T>(false, schema);
-
-                    let name = t.meta.name().clone();
-                    sql.push_str(&match change {
-                        CreateTable(_, _) => T::create_table(&name, schema),
-                        CreateTableIfNotExists(_, _) => {
-                            T::create_table_if_not_exists(&name, schema)
+        // Named enum types are created once, before the first table using them
+        let mut enums = HashSet::new();
+        let mut create_enums = |t: &Table| -> Vec<String> {
+            t.enum_types::<T>()
+                .into_iter()
+                .filter(|(name, _)| enums.insert(name.clone()))
+                .filter_map(|(name, variants)| T::create_enum(&name, &variants, schema))
+                .collect()
+        };
+
+        // Foreign keys that are only added once all tables exist
+        let mut deferred = vec![];
+        let analyze = changes.iter().filter(|c| matches!(c, Analyze(_))).count();
+
+        let mut groups: Vec<_> = changes
+            .iter_mut()
+            .map(|change| {
+                let description = Migration::describe(change);
+                // SQL written by the user, which is rendered as written
+                let mut user_sql = vec![];
+                let statements = match change {
+                    &mut CreateTable(ref mut t, ref mut cb)
+                    | &mut CreateTableIfNotExists(ref mut t, ref mut cb) => {
+                        cb(t); // Run the user code
+                        user_sql = t.user_sql();
+                        if self.fk_style == FkStyle::Constraint || self.defer_foreign_keys {
+                            t.foreign_keys_as_constraints();
                         }
-                        _ => unreachable!(),
-                    });
-                    sql.push_str(" (");
-                    let l = cols.len();
-                    for (i, slice) in cols.iter().enumerate() {
-                        sql.push_str(slice);
+                        if self.defer_foreign_keys {
+                            let alter = T::alter_table(&t.meta.name(), schema);
+                            deferred.extend(t.take_foreign_keys().into_iter().map(|(name, c)| {
+                                format!("{} {}", alter, T::add_constraint(true, schema, &name, &c))
+                            }));
+                        }
+                        if self.inline_indices {
+                            t.indices_inline::<T>();
+                        }
+                        let (cols, indices) = t.make::<T>(false, schema);
+                        let mut stmts = create_enums(t);
 
-                        if i < l - 1 {
-                            sql.push_str(", ");
+                        let name = t.meta.name().clone();
+                        let strict = t.meta.strict;
+                        let params = t.meta.storage_params.clone();
+                        let mut sql = match change {
+                            CreateTable(_, _) => T::create_table(&name, schema),
+                            CreateTableIfNotExists(_, _) => {
+                                T::create_table_if_not_exists(&name, schema)
+                            }
+                            _ => unreachable!(),
+                        };
+                        sql.push_str(" (");
+                        sql.push_str(&cols.join(", "));
+                        sql.push(')');
+
+                        if !params.is_empty() {
+                            sql.push_str(&T::storage_params(&params));
+                        }
+
+                        if strict {
+                            sql.push_str(&T::strict_table());
                         }
+
+                        stmts.push(sql);
+
+                        // Add additional index columns
+                        stmts.extend(indices);
+                        stmts
+                    }
+                    &mut CreateTableAs(ref name, ref select) => {
+                        vec![format!("{} AS {}", T::create_table(name, schema), select)]
                     }
-                    sql.push_str(")");
+                    &mut CreateTypedTable(ref mut t, ref type_name, ref mut cb) => {
+                        cb(t); // Run the user code
+                        user_sql = t.user_sql();
+                        let (cols, indices) = t.make::<T>(false, schema);
 
-                    // Add additional index columns
-                    if indices.len() > 0 {
-                        sql.push_str(";");
-                        sql.push_str(&indices.join(";"));
+                        let mut sql = T::create_typed_table(&t.meta.name(), type_name, schema);
+                        if !cols.is_empty() {
+                            sql.push_str(&format!(" ({})", cols.join(", ")));
+                        }
+
+                        let mut stmts = vec![sql];
+                        stmts.extend(indices);
+                        stmts
+                    }
+                    &mut CreateTableLike(ref name, ref source, ref including) => {
+                        vec![T::create_table_like(name, source, including, schema)]
+                    }
+                    &mut CreateView(ref view) => vec![T::create_view(
+                        &view.name,
+                        &view.select,
+                        view.or_replace,
+                        view.check_option,
+                        schema,
+                    )],
+                    &mut DropView(ref name) => vec![T::drop_view(name, schema)],
+                    &mut CreateMaterializedView(ref name, ref select) => {
+                        vec![T::create_materialized_view(name, select, schema)]
+                    }
+                    &mut DropMaterializedView(ref name) => {
+                        vec![T::drop_materialized_view(name, schema)]
+                    }
+                    &mut RefreshMaterializedView(ref name, concurrently) => {
+                        vec![T::refresh_materialized_view(name, concurrently, schema)]
+                    }
+                    &mut DropTable(ref name) => vec![T::drop_table(name, schema)],
+                    &mut DropTableIfExists(ref name) => {
+                        vec![T::drop_table_if_exists(name, schema)]
+                    }
+                    &mut DropTableRestrict(ref name) => {
+                        vec![T::drop_table_restrict(name, schema)]
+                    }
+                    &mut RenameTable(ref old, ref new) => {
+                        vec![T::rename_table(old, new, schema)]
+                    }
+                    &mut RenameEnumValue(ref name, ref old, ref new) => {
+                        vec![T::rename_enum_value(name, old, new, schema)]
+                    }
+                    &mut CreateSchema(ref name) => vec![T::create_schema(name)],
+                    &mut CreateSchemaIfNotExists(ref name) => {
+                        vec![T::create_schema_if_not_exists(name)]
+                    }
+                    &mut DropSchema(ref name, cascade) => vec![T::drop_schema(name, cascade)],
+                    &mut CommentOnSchema(ref name, ref comment) => {
+                        T::comment_on_schema(name, comment).into_iter().collect()
+                    }
+                    &mut Analyze(ref table) => {
+                        vec![T::analyze(table.as_ref().map(|t| t.as_str()), schema)]
+                    }
+                    &mut SetSession(ref key, ref value) => vec![T::set_session(key, value)],
+                    &mut MoveTableToSchema(ref name, ref new_schema) => {
+                        vec![T::move_table_to_schema(name, schema, new_schema)]
+                    }
+                    &mut SetTableOwner(ref table, ref role) => {
+                        vec![T::set_table_owner(table, role, schema)]
                     }
+                    &mut ClusterTable(ref table, ref index) => {
+                        vec![T::cluster_table(table, index, schema)]
+                    }
+                    &mut SetTableLogged(ref table, logged) => {
+                        vec![T::set_table_logged(table, logged, schema)]
+                    }
+                    &mut SetReplicaIdentity(ref table, ref identity) => {
+                        vec![T::set_replica_identity(table, identity, schema)]
+                    }
+                    &mut Grant(ref privileges, ref table, ref role) => {
+                        vec![T::grant(privileges, table, role, schema)]
+                    }
+                    &mut Revoke(ref privileges, ref table, ref role) => {
+                        vec![T::revoke(privileges, table, role, schema)]
+                    }
+                    &mut CreateSequence(ref name, start, increment) => {
+                        vec![T::create_sequence(name, start, increment, schema)]
+                    }
+                    &mut DropSequence(ref name) => vec![T::drop_sequence(name, schema)],
+                    &mut SetSequenceOwner(ref seq, ref table, ref column) => {
+                        vec![T::set_sequence_owner(seq, table, column, schema)]
+                    }
+                    &mut AttachPartition(ref parent, ref child, ref bounds) => {
+                        vec![T::attach_partition(parent, child, bounds, schema)]
+                    }
+                    &mut DetachPartition(ref parent, ref child) => {
+                        vec![T::detach_partition(parent, child, schema)]
+                    }
+                    &mut ChangeTable(ref mut t, ref mut cb) => {
+                        cb(t);
+                        user_sql = t.user_sql();
+                        if self.fk_style == FkStyle::Constraint {
+                            t.foreign_keys_as_constraints();
+                        }
+                        let (cols, indices) = t.make::<T>(true, schema);
+
+                        // Index changes are standalone statements, so only
+                        // emit an `ALTER TABLE` if there are column changes
+                        let mut stmts = create_enums(t);
+                        let alter = T::alter_table(&t.meta.name(), schema);
+                        if T::combines_alterations() && !cols.is_empty() {
+                            stmts.push(format!("{} {}", alter, cols.join(", ")));
+                        } else {
+                            stmts.extend(cols.iter().map(|col| format!("{} {}", alter, col)));
+                        }
+
+                        // Add additional index columns
+                        stmts.extend(indices);
+                        stmts
+                    }
+                    &mut InsertRows(ref table, ref columns, ref rows) => {
+                        vec![T::insert_rows(table, columns, rows, schema)]
+                    }
+                    &mut CustomLine(ref line) => Migration::split_statements(line),
+                    &mut CustomRaw(ref sql) => vec![sql.clone()],
+                };
+
+                match change {
+                    CreateTableAs(_, select) | CreateMaterializedView(_, select) => user_sql.push(select.clone()),
+                    CreateView(view) => user_sql.push(view.select.clone()),
+                    AttachPartition(_, _, bounds) => user_sql.push(bounds.clone()),
+                    CustomLine(_) => user_sql.extend(statements.clone()),
+                    _ => {}
                 }
-                &mut DropTable(ref name) => sql.push_str(&T::drop_table(name, schema)),
-                &mut DropTableIfExists(ref name) => {
-                    sql.push_str(&T::drop_table_if_exists(name, schema))
+
+                (description, statements, matches!(change, CustomRaw(_)), user_sql)
+            })
+            .collect();
+
+        if !deferred.is_empty() {
+            let at = groups.len() - analyze;
+            groups.insert(at, (String::from("Add deferred foreign keys"), deferred, false, vec![]));
+        }
+
+        options.render::<T>(groups)
+    }
+
+    /// Split custom SQL into its statements, dropping their `;`
+    ///
+    /// Semicolons inside of strings and quoted identifiers are kept.
+    fn split_statements(sql: &str) -> Vec<String> {
+        let mut stmts = vec![];
+        let mut current = String::new();
+        let mut quote = None;
+
+        for c in sql.chars() {
+            match quote {
+                Some(q) if c == q => quote = None,
+                Some(_) => {}
+                None if c == '\'' || c == '"' || c == '`' => quote = Some(c),
+                None if c == ';' => {
+                    stmts.push(current.trim().to_owned());
+                    current.clear();
+                    continue;
                 }
-                &mut RenameTable(ref old, ref new) => {
-                    sql.push_str(&T::rename_table(old, new, schema))
+                None => {}
+            }
+            current.push(c);
+        }
+        stmts.push(current.trim().to_owned());
+
+        stmts.into_iter().filter(|s| !s.is_empty()).collect()
+    }
+
+    /// A short, human readable description of a change
+    fn describe(change: &DatabaseChange) -> String {
+        use DatabaseChange::*;
+        match change {
+            CreateTable(t, _) => format!("Create table {}", t.meta.name),
+            CreateTableIfNotExists(t, _) => format!("Create table {} if it doesn't exist", t.meta.name),
+            CreateTableAs(name, _) => format!("Create table {} from a query", name),
+            CreateTypedTable(t, type_name, _) => format!("Create table {} of type {}", t.meta.name, type_name),
+            CreateTableLike(name, source, _) => format!("Create table {} like {}", name, source),
+            ChangeTable(t, _) => format!("Change table {}", t.meta.name),
+            RenameTable(old, new) => format!("Rename table {} to {}", old, new),
+            MoveTableToSchema(name, schema) => format!("Move table {} to schema {}", name, schema),
+            SetTableOwner(table, role) => format!("Make {} the owner of table {}", role, table),
+            ClusterTable(table, index) => format!("Cluster table {} on index {}", table, index),
+            SetTableLogged(table, true) => format!("Make table {} logged", table),
+            SetTableLogged(table, false) => format!("Make table {} unlogged", table),
+            SetReplicaIdentity(table, identity) => format!("Set replica identity of table {} to {}", table, identity),
+            Grant(_, table, role) => format!("Grant {} privileges on table {}", role, table),
+            Revoke(_, table, role) => format!("Revoke privileges of {} on table {}", role, table),
+            CreateSequence(name, _, _) => format!("Create sequence {}", name),
+            DropSequence(name) => format!("Drop sequence {}", name),
+            SetSequenceOwner(seq, table, column) => {
+                format!("Make sequence {} owned by {}.{}", seq, table, column)
+            }
+            AttachPartition(parent, child, _) => {
+                format!("Attach table {} as a partition of {}", child, parent)
+            }
+            DetachPartition(parent, child) => format!("Detach partition {} from {}", child, parent),
+            CreateView(view) => format!("Create view {}", view.name),
+            DropView(name) => format!("Drop view {}", name),
+            CreateMaterializedView(name, _) => format!("Create materialized view {}", name),
+            DropMaterializedView(name) => format!("Drop materialized view {}", name),
+            RefreshMaterializedView(name, _) => format!("Refresh materialized view {}", name),
+            DropTable(name) => format!("Drop table {}", name),
+            DropTableIfExists(name) => format!("Drop table {} if it exists", name),
+            DropTableRestrict(name) => format!("Drop table {} unless anything depends on it", name),
+            RenameEnumValue(name, old, new) => {
+                format!("Rename value {} of enum {} to {}", old, name, new)
+            }
+            CreateSchema(name) => format!("Create schema {}", name),
+            CreateSchemaIfNotExists(name) => format!("Create schema {} if it doesn't exist", name),
+            DropSchema(name, _) => format!("Drop schema {}", name),
+            CommentOnSchema(name, _) => format!("Comment on schema {}", name),
+            SetSession(key, _) => format!("Set session {}", key),
+            Analyze(Some(table)) => format!("Analyze table {}", table),
+            Analyze(None) => String::from("Analyze all tables"),
+            InsertRows(table, _, rows) => format!("Insert {} rows into table {}", rows.len(), table),
+            CustomLine(_) | CustomRaw(_) => String::from("Custom SQL"),
+        }
+    }
+
+    /// Check this migration for mistakes that would generate invalid SQL
+    ///
+    /// Like `make`, this runs all table callbacks on a copy of the
+    /// migration, and returns the first problem it finds.
+    pub fn validate<T: SqlGenerator>(&self) -> Result<(), ValidationError> {
+        use DatabaseChange::*;
+
+        let mut changes = self.prefixed_changes();
+        for change in changes.iter_mut() {
+            match change {
+                &mut CreateTable(ref mut t, ref mut cb)
+                | &mut CreateTableIfNotExists(ref mut t, ref mut cb)
+                | &mut CreateTypedTable(ref mut t, _, ref mut cb) => {
+                    cb(t);
+                    t.validate::<T>()?;
                 }
                 &mut ChangeTable(ref mut t, ref mut cb) => {
                     cb(t);
-                    let (cols, indices) = t.make::<T>(true, schema);
-                    sql.push_str(&T::alter_table(&t.meta.name(), schema));
-                    sql.push_str(" ");
-                    let l = cols.len();
-                    for (i, slice) in cols.iter().enumerate() {
-                        sql.push_str(slice);
-
-                        if i < l - 1 {
-                            sql.push_str(", ");
-                        }
-                    }
+                    t.validate::<T>()?;
+                    t.validate_added_columns()?;
+                }
+                &mut CreateTableAs(ref name, _)
+                | &mut CreateTableLike(ref name, _, _)
+                | &mut RenameTable(_, ref name) => validate_identifier::<T>(name)?,
+                &mut CreateView(ref view) => validate_identifier::<T>(&view.name)?,
+                &mut CreateSequence(ref name, _, _)
+                | &mut CreateSchema(ref name)
+                | &mut CreateSchemaIfNotExists(ref name) => validate_identifier::<T>(name)?,
+                &mut CreateMaterializedView(ref name, _) => validate_identifier::<T>(name)?,
+                _ => {}
+            }
+        }
 
-                    // Add additional index columns
-                    if indices.len() > 0 {
-                        sql.push_str(";");
-                        sql.push_str(&indices.join(";"));
-                    }
+        Ok(())
+    }
+
+    /// Render this migration together with the one that reverts it
+    ///
+    /// Returns the `up` and `down` SQL, in that order, as most
+    /// migration tools store them.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// let mut up = Migration::new();
+    /// up.create_table("users", |t| {
+    ///     t.add_column("name", types::text());
+    /// });
+    ///
+    /// let mut down = Migration::new();
+    /// down.drop_table("users");
+    /// # #[cfg(feature = "pg")]
+    /// let (up_sql, down_sql) = up.make_up_down::<barrel::backend::Pg>(&down);
+    /// ```
+    pub fn make_up_down<T: SqlGenerator>(&self, down: &Migration) -> (String, String) {
+        (self.make::<T>(), down.make::<T>())
+    }
+
+    /// A stable hash of the SQL that `make` generates, as hex
+    ///
+    /// Migration tools can store it to notice when a migration was
+    /// changed after it ran. The hash is a 64-bit FNV-1a, which is
+    /// the same across platforms and releases, but not meant to be
+    /// secure.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// # let mut m = Migration::new();
+    /// # #[cfg(feature = "pg")]
+    /// let checksum = m.checksum::<barrel::backend::Pg>();
+    /// ```
+    pub fn checksum<T: SqlGenerator>(&self) -> String {
+        let hash = self.make::<T>().bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+        format!("{:016x}", hash)
+    }
+
+    /// Count the changes of this migration by kind, e.g. for logging
+    ///
+    /// Like `validate`, this runs all table callbacks on a copy of
+    /// the migration to find their indices.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.drop_table("sessions");
+    /// assert_eq!(m.summary().drops, 1);
+    /// ```
+    pub fn summary(&self) -> Summary {
+        use DatabaseChange::*;
+
+        let mut summary = Summary::default();
+        for change in self.prefixed_changes().iter_mut() {
+            match change {
+                &mut CreateTable(ref mut t, ref mut cb)
+                | &mut CreateTableIfNotExists(ref mut t, ref mut cb)
+                | &mut CreateTypedTable(ref mut t, _, ref mut cb) => {
+                    cb(t);
+                    summary.creates += 1;
+                    summary.indexes += t.index_changes();
                 }
+                &mut ChangeTable(ref mut t, ref mut cb) => {
+                    cb(t);
+                    summary.alters += 1;
+                    summary.indexes += t.index_changes();
+                }
+                &mut CreateTableAs(_, _)
+                | &mut CreateTableLike(_, _, _)
+                | &mut CreateSequence(_, _, _)
+                | &mut CreateView(_)
+                | &mut CreateMaterializedView(_, _)
+                | &mut CreateSchema(_)
+                | &mut CreateSchemaIfNotExists(_) => summary.creates += 1,
+                &mut DropTable(_)
+                | &mut DropTableIfExists(_)
+                | &mut DropTableRestrict(_)
+                | &mut DropSequence(_)
+                | &mut DropView(_)
+                | &mut DropMaterializedView(_)
+                | &mut DropSchema(_, _) => summary.drops += 1,
+                &mut RenameTable(_, _)
+                | &mut MoveTableToSchema(_, _)
+                | &mut SetTableOwner(_, _)
+                | &mut ClusterTable(_, _)
+                | &mut SetTableLogged(_, _)
+                | &mut SetReplicaIdentity(_, _)
+                | &mut SetSequenceOwner(_, _, _)
+                | &mut AttachPartition(_, _, _)
+                | &mut DetachPartition(_, _)
+                | &mut RenameEnumValue(_, _, _) => summary.alters += 1,
+                &mut Grant(_, _, _)
+                | &mut Revoke(_, _, _)
+                | &mut RefreshMaterializedView(_, _)
+                | &mut SetSession(_, _)
+                | &mut CommentOnSchema(_, _)
+                | &mut Analyze(_)
+                | &mut InsertRows(_, _, _)
+                | &mut CustomLine(_)
+                | &mut CustomRaw(_) => {}
             }
+        }
 
-            sql.push_str(";");
-            sql
-        })
+        summary
+    }
+
+    /// The same as `make` but runs `validate` first
+    pub fn try_make<T: SqlGenerator>(&self) -> Result<String, ValidationError> {
+        self.validate::<T>()?;
+        Ok(self.make::<T>())
     }
 
     /// The same as `make` but making a run-time check for sql variant
@@ -131,7 +675,36 @@ impl Migration {
         variant.run_for(self)
     }
 
-    
+
+    /// Insert rows into a table, e.g. to seed a lookup table
+    ///
+    /// Each row has a value for every one of `columns`. The values
+    /// are rendered like column defaults, with strings escaped and
+    /// dates as ISO-8601 timestamps in UTC. MySQL gets booleans as
+    /// `TRUE` or `FALSE`, Sqlite as `1` or `0`.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.insert_rows(
+    ///     "roles",
+    ///     &["name", "level"],
+    ///     vec![vec!["admin".into(), 10.into()], vec!["guest".into(), 0.into()]],
+    /// );
+    /// ```
+    pub fn insert_rows<S: Into<String>>(
+        &mut self,
+        table: S,
+        columns: &[&str],
+        rows: Vec<Vec<WrappedDefault<'static>>>,
+    ) {
+        self.changes.push(DatabaseChange::InsertRows(
+            table.into(),
+            columns.iter().map(|c| c.to_string()).collect(),
+            rows,
+        ));
+    }
+
     /// Inject a line of custom SQL into the top-level migration scope
     ///
     /// This is a bypass to the barrel typesystem, in case there is
@@ -140,10 +713,98 @@ impl Migration {
     /// It does however mean that the SQL provided needs to be
     /// specific for one database, meaning that future migrations
     /// might become cumbersome.
+    ///
+    /// The SQL may contain several statements. Each of them is
+    /// terminated like any other statement, so a trailing `;` is
+    /// optional.
     pub fn inject_custom<S: Into<String>>(&mut self, sql: S) {
         self.changes.push(DatabaseChange::CustomLine(sql.into()));
     }
-    
+
+    /// Inject custom SQL that is not changed in any way
+    ///
+    /// Unlike `inject_custom`, no statements are split or terminated,
+    /// and render options don't apply. Use this for SQL that can't be
+    /// split on `;`, like function bodies.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.inject_custom_raw("CREATE FUNCTION one() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql;");
+    /// ```
+    pub fn inject_custom_raw<S: Into<String>>(&mut self, sql: S) {
+        self.changes.push(DatabaseChange::CustomRaw(sql.into()));
+    }
+
+    /// Rename a value of an enum type created by `types::enumeration`
+    ///
+    /// Rows that use the value keep it under the new name. This is
+    /// only supported by Postgres (10+).
+    pub fn rename_enum_value<S: Into<String>>(&mut self, name: S, old: S, new: S) {
+        self.changes.push(DatabaseChange::RenameEnumValue(
+            name.into(),
+            old.into(),
+            new.into(),
+        ));
+    }
+
+    /// Create a new schema
+    ///
+    /// MySQL schemas are databases, Sqlite doesn't support this.
+    pub fn create_schema<S: Into<String>>(&mut self, name: S) {
+        self.changes.push(DatabaseChange::CreateSchema(name.into()));
+    }
+
+    /// Create a new schema *only* if it doesn't exist yet
+    pub fn create_schema_if_not_exists<S: Into<String>>(&mut self, name: S) {
+        self.changes
+            .push(DatabaseChange::CreateSchemaIfNotExists(name.into()));
+    }
+
+    /// Drop a schema
+    ///
+    /// With `cascade`, all tables and other objects in the schema are
+    /// dropped too, otherwise the schema must be empty. MySQL always
+    /// drops the contents and needs `cascade` to be set.
+    pub fn drop_schema<S: Into<String>>(&mut self, name: S, cascade: bool) {
+        self.changes
+            .push(DatabaseChange::DropSchema(name.into(), cascade));
+    }
+
+    /// Describe what a schema is for
+    ///
+    /// Only Postgres stores comments on schemas, other backends
+    /// leave this out.
+    pub fn comment_schema<S: Into<String>>(&mut self, name: S, comment: S) {
+        self.changes
+            .push(DatabaseChange::CommentOnSchema(name.into(), comment.into()));
+    }
+
+    /// Change a setting for the session running this migration
+    ///
+    /// The value is passed through as raw SQL. Settings only affect
+    /// the changes that come after them, so this is usually called
+    /// first. Not supported by Sqlite.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.set_session("lock_timeout", "'5s'");
+    /// ```
+    pub fn set_session<S: Into<String>>(&mut self, key: S, value: S) {
+        self.changes
+            .push(DatabaseChange::SetSession(key.into(), value.into()));
+    }
+
+    /// Collect planner statistics for a table, or the whole database
+    ///
+    /// This is always run after all other changes of the migration,
+    /// regardless of when it was added. MySQL needs a table name.
+    pub fn analyze(&mut self, table: Option<&str>) {
+        self.changes
+            .push(DatabaseChange::Analyze(table.map(|t| t.into())));
+    }
+
     /// Automatically infer the `down` step of this migration
     ///
     /// Will thrown an error if behaviour is ambiguous or not
@@ -172,6 +833,35 @@ impl Migration {
         }
     }
 
+    /// Create a new table with the columns of a composite type
+    ///
+    /// The columns come from the type, so the callback should only
+    /// add constraints and indices. This is only supported by Postgres.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.inject_custom("CREATE TYPE address AS (street TEXT, city TEXT)");
+    /// m.create_typed_table("addresses", "address", |t| {
+    ///     t.add_unique(&["street", "city"]);
+    /// });
+    /// ```
+    pub fn create_typed_table<S: Into<String>, F>(&mut self, name: S, type_name: S, cb: F) -> &mut TableMeta
+    where
+        F: Fn(&mut Table) + 'static,
+    {
+        self.changes.push(DatabaseChange::CreateTypedTable(
+            Table::new(name),
+            type_name.into(),
+            Rc::new(cb),
+        ));
+
+        match self.changes.last_mut().unwrap() {
+            &mut DatabaseChange::CreateTypedTable(ref mut t, _, _) => &mut t.meta,
+            _ => unreachable!(),
+        }
+    }
+
     /// Create a new table *only* if it doesn't exist yet
     pub fn create_table_if_not_exists<S: Into<String>, F: 'static>(
         &mut self,
@@ -192,6 +882,34 @@ impl Migration {
         }
     }
 
+    /// Create a new table from the rows of a `SELECT` query
+    ///
+    /// The query is raw SQL and passed through as-is, columns and
+    /// their types are taken from its result.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.create_table_as("active_users", "SELECT * FROM users WHERE active");
+    /// ```
+    pub fn create_table_as<S: Into<String>>(&mut self, name: S, select: S) {
+        self.changes
+            .push(DatabaseChange::CreateTableAs(name.into(), select.into()));
+    }
+
+    /// Create a new table with the same columns as an existing one
+    ///
+    /// `including` lists what Postgres copies besides the columns,
+    /// e.g. `&["ALL"]` or `&["DEFAULTS", "INDEXES"]`. MySQL always
+    /// copies everything and ignores it. Not supported by Sqlite.
+    pub fn create_table_like<S: Into<String>>(&mut self, name: S, source: S, including: &[&str]) {
+        self.changes.push(DatabaseChange::CreateTableLike(
+            name.into(),
+            source.into(),
+            including.iter().map(|i| (*i).into()).collect(),
+        ));
+    }
+
     /// Change fields on an existing table
     pub fn change_table<S: Into<String>, F: 'static>(&mut self, name: S, cb: F)
     where
@@ -208,6 +926,193 @@ impl Migration {
             .push(DatabaseChange::RenameTable(old.into(), new.into()));
     }
 
+    /// Move a table from the migration schema into another one
+    pub fn move_table_to_schema<S: Into<String>>(&mut self, name: S, schema: S) {
+        self.changes
+            .push(DatabaseChange::MoveTableToSchema(name.into(), schema.into()));
+    }
+
+    /// Make a role the owner of a table
+    ///
+    /// This is only supported by Postgres.
+    pub fn set_table_owner<S: Into<String>>(&mut self, table: S, role: S) {
+        self.changes
+            .push(DatabaseChange::SetTableOwner(table.into(), role.into()));
+    }
+
+    /// Reorder the rows of a table to match one of its indices
+    ///
+    /// This is a one-time operation, new rows aren't kept in order.
+    /// It also locks the table while it runs. Only supported by Postgres.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.cluster_table("events", "events_created_at_idx");
+    /// ```
+    pub fn cluster_table<S: Into<String>>(&mut self, table: S, index: S) {
+        self.changes
+            .push(DatabaseChange::ClusterTable(table.into(), index.into()));
+    }
+
+    /// Write changes to a table to the write-ahead log, or stop doing so
+    ///
+    /// Unlogged tables are faster to write, but are emptied after a
+    /// crash. This is only supported by Postgres.
+    pub fn set_table_logged<S: Into<String>>(&mut self, table: S, logged: bool) {
+        self.changes
+            .push(DatabaseChange::SetTableLogged(table.into(), logged));
+    }
+
+    /// Change how rows of a table are identified in logical replication
+    ///
+    /// The identity is passed as-is: `DEFAULT`, `FULL`, `NOTHING` or
+    /// `USING INDEX <name>`. This is only supported by Postgres.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.set_replica_identity("users", "FULL");
+    /// ```
+    pub fn set_replica_identity<S: Into<String>>(&mut self, table: S, identity: S) {
+        self.changes
+            .push(DatabaseChange::SetReplicaIdentity(table.into(), identity.into()));
+    }
+
+    /// Give a role privileges on a table
+    ///
+    /// Privileges are SQL keywords like `SELECT` or `ALL PRIVILEGES`.
+    /// Not supported by Sqlite.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.grant(&["SELECT", "INSERT"], "users", "app");
+    /// ```
+    pub fn grant<S: Into<String>>(&mut self, privileges: &[&str], table: S, role: S) {
+        self.changes.push(DatabaseChange::Grant(
+            privileges.iter().map(|p| (*p).into()).collect(),
+            table.into(),
+            role.into(),
+        ));
+    }
+
+    /// Take privileges on a table away from a role
+    ///
+    /// Not supported by Sqlite.
+    pub fn revoke<S: Into<String>>(&mut self, privileges: &[&str], table: S, role: S) {
+        self.changes.push(DatabaseChange::Revoke(
+            privileges.iter().map(|p| (*p).into()).collect(),
+            table.into(),
+            role.into(),
+        ));
+    }
+
+    /// Create a sequence of numbers, for keys that are managed by hand
+    ///
+    /// The sequence starts at `start` and counts in steps of
+    /// `increment`. This is only supported by Postgres.
+    pub fn create_sequence<S: Into<String>>(&mut self, name: S, start: i64, increment: i64) {
+        self.changes
+            .push(DatabaseChange::CreateSequence(name.into(), start, increment));
+    }
+
+    /// Drop an existing sequence
+    ///
+    /// This is only supported by Postgres.
+    pub fn drop_sequence<S: Into<String>>(&mut self, name: S) {
+        self.changes.push(DatabaseChange::DropSequence(name.into()));
+    }
+
+    /// Make a sequence owned by a table column
+    ///
+    /// An owned sequence is dropped together with its column. This is
+    /// only supported by Postgres, which already does it for `SERIAL`
+    /// columns when they are created.
+    pub fn set_sequence_owner<S: Into<String>>(&mut self, sequence: S, table: S, column: S) {
+        self.changes.push(DatabaseChange::SetSequenceOwner(
+            sequence.into(),
+            table.into(),
+            column.into(),
+        ));
+    }
+
+    /// Attach an existing table as a partition of a partitioned table
+    ///
+    /// `bounds` is the raw partition bound that follows `FOR VALUES`,
+    /// e.g. `FROM ('2020-01-01') TO ('2021-01-01')` or `IN ('eu')`.
+    /// This is only supported by Postgres.
+    pub fn attach_partition<S: Into<String>>(&mut self, parent: S, child: S, bounds: S) {
+        self.changes.push(DatabaseChange::AttachPartition(
+            parent.into(),
+            child.into(),
+            bounds.into(),
+        ));
+    }
+
+    /// Detach a partition, which then becomes a regular table
+    ///
+    /// This is only supported by Postgres.
+    pub fn detach_partition<S: Into<String>>(&mut self, parent: S, child: S) {
+        self.changes
+            .push(DatabaseChange::DetachPartition(parent.into(), child.into()));
+    }
+
+    /// Create a view for a `SELECT` query
+    ///
+    /// The query is raw SQL and passed through as-is.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// let mut m = Migration::new();
+    /// m.create_view("active_users", "SELECT * FROM users WHERE active")
+    ///     .or_replace(true);
+    /// ```
+    pub fn create_view<S: Into<String>>(&mut self, name: S, select: S) -> &mut ViewMeta {
+        self.changes
+            .push(DatabaseChange::CreateView(ViewMeta::new(name.into(), select.into())));
+
+        match self.changes.last_mut().unwrap() {
+            &mut DatabaseChange::CreateView(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Drop an existing view
+    pub fn drop_view<S: Into<String>>(&mut self, name: S) {
+        self.changes.push(DatabaseChange::DropView(name.into()));
+    }
+
+    /// Create a materialized view, which stores the result of a `SELECT` query
+    ///
+    /// The query is raw SQL and passed through as-is. This is only
+    /// supported by Postgres.
+    pub fn create_materialized_view<S: Into<String>>(&mut self, name: S, select: S) {
+        self.changes.push(DatabaseChange::CreateMaterializedView(
+            name.into(),
+            select.into(),
+        ));
+    }
+
+    /// Drop an existing materialized view
+    ///
+    /// This is only supported by Postgres.
+    pub fn drop_materialized_view<S: Into<String>>(&mut self, name: S) {
+        self.changes
+            .push(DatabaseChange::DropMaterializedView(name.into()));
+    }
+
+    /// Re-run the query of a materialized view and store the new result
+    ///
+    /// With `concurrently`, reads aren't blocked while refreshing, but
+    /// the view needs a unique index. This is only supported by Postgres.
+    pub fn refresh_materialized_view<S: Into<String>>(&mut self, name: S, concurrently: bool) {
+        self.changes.push(DatabaseChange::RefreshMaterializedView(
+            name.into(),
+            concurrently,
+        ));
+    }
+
     /// Drop an existing table
     pub fn drop_table<S: Into<String>>(&mut self, name: S) {
         self.changes.push(DatabaseChange::DropTable(name.into()));
@@ -218,4 +1123,13 @@ impl Migration {
         self.changes
             .push(DatabaseChange::DropTableIfExists(name.into()));
     }
+
+    /// Drop a table, but fail if views or foreign keys depend on it
+    ///
+    /// Postgres renders `RESTRICT`. It is what every backend does by
+    /// default, so the others drop the table as usual.
+    pub fn drop_table_restrict<S: Into<String>>(&mut self, name: S) {
+        self.changes
+            .push(DatabaseChange::DropTableRestrict(name.into()));
+    }
 }
@@ -0,0 +1,233 @@
+//! Options that control how a migration is turned into SQL
+//!
+//! `Migration::make` renders all statements on a single line, each
+//! terminated by a `;`. `Migration::make_with` takes `RenderOptions`
+//! to change that layout, without changing the statements themselves.
+
+use crate::backend::SqlGenerator;
+
+/// The case used for SQL keywords
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeywordCase {
+    /// Keywords as generated, which is upper case
+    Upper,
+    /// Keywords in lower case
+    ///
+    /// SQL written by the user, like the query of a view, custom SQL or
+    /// the expressions of a column, is kept as written.
+    Lower,
+}
+
+/// When identifiers are put in quotes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quoting {
+    /// Every identifier is quoted, which is how they are generated
+    Always,
+    /// Only identifiers that are reserved words, or that aren't
+    /// plain lower case names, are quoted
+    Minimal,
+}
+
+/// Formatting toggles for `Migration::make_with`
+///
+/// The defaults match the output of `Migration::make`: everything on
+/// one line, no comments, upper case keywords and a `;` after every
+/// statement. New rendering flags are added here.
+///
+/// ```rust
+/// # use barrel::RenderOptions;
+/// let options = RenderOptions::new().multiline(true).comments(true);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// Put every statement on its own line (default: `false`)
+    pub multiline: bool,
+    /// Describe every change in a `--` comment before its statements (default: `false`)
+    pub comments: bool,
+    /// The case of SQL keywords (default: `KeywordCase::Upper`)
+    pub keyword_case: KeywordCase,
+    /// Terminate statements with `;` (default: `true`)
+    ///
+    /// Without semicolons, statements are always put on separate lines.
+    pub semicolons: bool,
+    /// When identifiers are quoted (default: `Quoting::Always`)
+    pub quoting: Quoting,
+    /// What is put after every statement (default: `";"`)
+    ///
+    /// Only used with `semicolons` set. Statements aren't separated
+    /// further when it ends in a line break.
+    pub terminator: String,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            multiline: false,
+            comments: false,
+            keyword_case: KeywordCase::Upper,
+            semicolons: true,
+            quoting: Quoting::Always,
+            terminator: String::from(";"),
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Create options with the default values
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Put every statement on its own line
+    pub fn multiline(self, arg: bool) -> Self {
+        Self { multiline: arg, ..self }
+    }
+
+    /// Describe every change in a `--` comment before its statements
+    pub fn comments(self, arg: bool) -> Self {
+        Self { comments: arg, ..self }
+    }
+
+    /// Choose the case of SQL keywords
+    pub fn keyword_case(self, arg: KeywordCase) -> Self {
+        Self { keyword_case: arg, ..self }
+    }
+
+    /// Terminate statements with `;`
+    pub fn semicolons(self, arg: bool) -> Self {
+        Self { semicolons: arg, ..self }
+    }
+
+    /// Choose when identifiers are quoted
+    pub fn quoting(self, arg: Quoting) -> Self {
+        Self { quoting: arg, ..self }
+    }
+
+    /// End statements with `terminator` instead of `;`, e.g. `$$`
+    ///
+    /// ```rust
+    /// # use barrel::RenderOptions;
+    /// let options = RenderOptions::new().terminator(";\n");
+    /// ```
+    pub fn terminator<S: Into<String>>(self, arg: S) -> Self {
+        Self { terminator: arg.into(), ..self }
+    }
+
+    /// Join groups of statements, each with a description, into one string
+    ///
+    /// Statements of raw groups are added as they are. The SQL written
+    /// by the user in the statements of a group keeps its case.
+    pub(crate) fn render<T: SqlGenerator>(&self, groups: Vec<(String, Vec<String>, bool, Vec<String>)>) -> String {
+        let separator = match self.multiline || !self.semicolons {
+            true => "\n",
+            false => "",
+        };
+
+        let mut sql = String::new();
+        for (description, statements, raw, user_sql) in groups {
+            if self.comments {
+                if !sql.is_empty() {
+                    sql.push('\n');
+                }
+                sql.push_str(&format!("-- {}\n", description));
+            }
+
+            for stmt in statements {
+                if !sql.is_empty() && !sql.ends_with('\n') {
+                    sql.push_str(separator);
+                }
+                if raw {
+                    sql.push_str(&stmt);
+                    continue;
+                }
+                let stmt = self.apply_case(&stmt, &user_sql);
+                match self.quoting {
+                    Quoting::Always => sql.push_str(&stmt),
+                    Quoting::Minimal => sql.push_str(&Self::unquote::<T>(&stmt)),
+                }
+                if self.semicolons {
+                    sql.push_str(&self.terminator);
+                }
+            }
+        }
+
+        sql
+    }
+
+    /// Change the case of everything that isn't quoted or written by the user
+    fn apply_case(&self, stmt: &str, user_sql: &[String]) -> String {
+        match self.keyword_case {
+            KeywordCase::Upper => stmt.to_owned(),
+            KeywordCase::Lower => {
+                // Mark the bytes of the user's SQL, which are kept as they are
+                let mut kept = vec![false; stmt.len()];
+                for sql in user_sql.iter().filter(|sql| !sql.is_empty()) {
+                    for (at, _) in stmt.match_indices(sql.as_str()) {
+                        kept[at..at + sql.len()].iter_mut().for_each(|k| *k = true);
+                    }
+                }
+
+                let mut quote = None;
+                stmt.char_indices()
+                    .map(|(i, c)| match quote {
+                        _ if kept[i] => c,
+                        Some(q) if c == q => {
+                            quote = None;
+                            c
+                        }
+                        Some(_) => c,
+                        None if c == '"' || c == '`' || c == '\'' => {
+                            quote = Some(c);
+                            c
+                        }
+                        None => c.to_ascii_lowercase(),
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Remove the quotes around identifiers that don't need them
+    fn unquote<T: SqlGenerator>(stmt: &str) -> String {
+        let quote = T::identifier_quote();
+        let mut sql = String::new();
+        let mut chars = stmt.chars().peekable();
+        let mut string = false;
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\'' => string = !string,
+                c if c == quote && !string => {
+                    // A doubled quote is a quote inside of the identifier
+                    let mut ident = String::new();
+                    while let Some(c) = chars.next() {
+                        if c != quote {
+                            ident.push(c);
+                        } else if chars.peek() == Some(&quote) {
+                            ident.push(chars.next().unwrap());
+                        } else {
+                            break;
+                        }
+                    }
+                    let plain = ident.starts_with(|c: char| c.is_ascii_lowercase() || c == '_')
+                        && ident
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+                    if plain && !T::is_reserved(&ident) {
+                        sql.push_str(&ident);
+                    } else {
+                        sql.push(quote);
+                        sql.push_str(&ident.replace(quote, &format!("{}{}", quote, quote)));
+                        sql.push(quote);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            sql.push(c);
+        }
+
+        sql
+    }
+}
@@ -7,9 +7,10 @@
 //! You can also change existing tables with a closure that can
 //! then access individual columns in that table.
 
-use super::backend::SqlGenerator;
+use super::backend::{SqlGenerator, SqlVariant};
 use super::{IndexChange, TableChange};
-use crate::types::Type;
+use crate::errors::ValidationError;
+use crate::types::{self, BaseType, ConstraintType, Type, WrappedDefault};
 use std::fmt::{Debug, Formatter, Result as FmtResult};
 
 impl Debug for TableChange {
@@ -24,6 +25,22 @@ impl Debug for IndexChange {
     }
 }
 
+/// A column type with a default name, to be added to many tables
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnTemplate {
+    pub name: String,
+    pub column: Type,
+}
+
+impl ColumnTemplate {
+    pub fn new<S: Into<String>>(name: S, column: Type) -> Self {
+        Self {
+            name: name.into(),
+            column,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Table {
     pub meta: TableMeta,
@@ -60,15 +77,316 @@ impl Table {
         }
     }
 
+    /// The names of all added columns, in the order they were added
+    ///
+    /// Columns from `add_column_for` are left out, since they only
+    /// exist for one backend.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Table};
+    /// let mut t = Table::new("users");
+    /// t.add_column("id", types::primary());
+    /// t.add_column("name", types::text());
+    /// assert_eq!(t.column_names(), vec!["id", "name"]);
+    /// ```
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns
+            .iter()
+            .filter_map(|change| match change {
+                TableChange::AddColumn(name, _) => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Add a column from a template that is shared between tables
+    ///
+    /// ```rust
+    /// # use barrel::{types, ColumnTemplate, Migration};
+    /// # let mut m = Migration::new();
+    /// let id = ColumnTemplate::new("id", types::primary());
+    /// m.create_table("users", move |t| {
+    ///     t.apply_template(&id);
+    /// });
+    /// ```
+    pub fn apply_template(&mut self, template: &ColumnTemplate) -> &mut Type {
+        self.add_column(template.name.clone(), template.column.clone())
+    }
+
+    /// Add a new column that only exists for one database backend
+    ///
+    /// When generating SQL for any other backend this column is
+    /// skipped. This lets one migration cover backend-specific types.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// # m.create_table("users", |table| {
+    /// # #[cfg(feature = "pg")]
+    /// table.add_column_for(barrel::SqlVariant::Pg, "tags", types::array(&types::text()));
+    /// # });
+    /// ```
+    pub fn add_column_for<S: Into<String>>(
+        &mut self,
+        variant: SqlVariant,
+        name: S,
+        _type: Type,
+    ) -> &mut Type {
+        self.columns
+            .push(TableChange::AddColumnFor(variant, name.into(), _type));
+
+        match self.columns.last_mut().unwrap() {
+            &mut TableChange::AddColumnFor(_, _, ref mut c) => c,
+            _ => unreachable!(),
+        }
+    }
+
     pub fn drop_column<S: Into<String>>(&mut self, name: S) {
         self.columns.push(TableChange::DropColumn(name.into()));
     }
 
+    /// Remove several columns at once
+    ///
+    /// Postgres and MySQL drop them in one `ALTER TABLE`, Sqlite
+    /// needs a statement for each column.
+    ///
+    /// ```rust
+    /// # use barrel::Migration;
+    /// # let mut m = Migration::new();
+    /// m.change_table("users", |t| {
+    ///     t.drop_columns(&["nickname", "legacy_id"]);
+    /// });
+    /// ```
+    pub fn drop_columns(&mut self, names: &[&str]) {
+        for name in names {
+            self.drop_column(*name);
+        }
+    }
+
     pub fn rename_column<S: Into<String>>(&mut self, old: S, new: S) {
         self.columns
             .push(TableChange::RenameColumn(old.into(), new.into()));
     }
 
+    /// Recreate this table with new column definitions, keeping all rows
+    ///
+    /// Sqlite can't change existing columns, so instead a new table
+    /// is created with `columns`, the rows are copied over, and the
+    /// new table replaces the old one. Every column in `columns` must
+    /// already exist in the old table. Indices are dropped with the
+    /// old table and have to be added again.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.change_table("users", |t| {
+    ///     t.rebuild_for_column_change(vec![
+    ///         ("id", types::primary()),
+    ///         ("name", types::varchar(255).nullable(true)),
+    ///     ]);
+    /// });
+    /// ```
+    pub fn rebuild_for_column_change<S: Into<String>>(&mut self, columns: Vec<(S, Type)>) {
+        self.columns.push(TableChange::Rebuild(
+            columns.into_iter().map(|(n, c)| (n.into(), c)).collect(),
+        ));
+    }
+
+    /// Remove the primary key of this table
+    ///
+    /// Postgres drops the constraint by its default name (`<table>_pkey`).
+    /// Not supported by Sqlite.
+    pub fn drop_primary_key(&mut self) {
+        self.columns.push(TableChange::DropPrimaryKey);
+    }
+
+    /// Change how the text of a column is compared and sorted
+    ///
+    /// Postgres changes the collation together with the type, and
+    /// MySQL restates the whole column, so both need its `Type`. Not
+    /// supported by Sqlite.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.change_table("users", |t| {
+    ///     t.set_collation("name", types::text(), "C");
+    /// });
+    /// ```
+    pub fn set_collation<S: Into<String>>(&mut self, column: S, _type: Type, collation: S) {
+        self.columns
+            .push(TableChange::SetCollation(column.into(), collation.into(), _type));
+    }
+
+    /// Change the default value of a column
+    ///
+    /// This is a single `SET DEFAULT`, which overwrites the previous
+    /// default, so there is no need to drop it first. Existing rows
+    /// keep their values. Not supported by Sqlite.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.change_table("users", |t| {
+    ///     t.change_default("plan", "free");
+    /// });
+    /// ```
+    pub fn change_default<S: Into<String>>(&mut self, column: S, value: impl Into<WrappedDefault<'static>>) {
+        self.columns.push(TableChange::SetDefault(column.into(), value.into()));
+    }
+
+    /// Turn an existing integer column into an identity column
+    ///
+    /// Only supported by Postgres. The sequence starts at 1, so
+    /// existing rows may need `inject_custom` to restart it.
+    ///
+    /// ```rust
+    /// # use barrel::{types::Identity, Migration};
+    /// # let mut m = Migration::new();
+    /// m.change_table("users", |t| {
+    ///     t.add_identity("id", Identity::ByDefault);
+    /// });
+    /// ```
+    pub fn add_identity<S: Into<String>>(&mut self, column: S, mode: types::Identity) {
+        self.columns.push(TableChange::AddIdentity(column.into(), mode));
+    }
+
+    /// Turn an identity column into a plain column, keeping its values
+    ///
+    /// Only supported by Postgres. MySQL has to restate the column
+    /// without `AUTO_INCREMENT`, which needs `inject_custom`.
+    pub fn drop_identity<S: Into<String>>(&mut self, column: S) {
+        self.columns.push(TableChange::DropIdentity(column.into()));
+    }
+
+    /// Add a nullable `deleted_at` timestamp to mark rows as soft-deleted
+    ///
+    /// With `indexed` set, a partial index over all rows that are
+    /// *not* deleted is created as well (`<table>_deleted_at_idx`).
+    /// Partial indices are not supported by MySQL.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.create_table("posts", |t| {
+    ///     t.add_column("id", types::primary());
+    ///     t.add_soft_delete(true);
+    /// });
+    /// ```
+    pub fn add_soft_delete(&mut self, indexed: bool) {
+        self.add_column("deleted_at", types::datetime().nullable(true));
+
+        if indexed {
+            let name = format!("{}_deleted_at_idx", self.meta.name);
+            self.add_partial_index(name, types::index(vec!["deleted_at"]), "deleted_at IS NULL");
+        }
+    }
+
+    /// Index a text search column with GIN, which is what text search queries use
+    ///
+    /// The index is named `<table>_<column>_idx`. Only useful on
+    /// Postgres, for `tsvector` columns.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.create_table("posts", |t| {
+    ///     t.add_column("search", types::tsvector());
+    ///     t.add_fulltext_gin("search");
+    /// });
+    /// ```
+    pub fn add_fulltext_gin(&mut self, column: &str) {
+        let name = format!("{}_{}_idx", self.meta.name, column);
+        self.add_index(name, types::index(vec![column]).index_method("gin"));
+    }
+
+    /// Add `created_at` and `updated_at` timestamps to a table
+    ///
+    /// Both default to the time a row is inserted. On MySQL,
+    /// `updated_at` is also refreshed whenever a row is updated.
+    pub fn add_timestamps(&mut self) {
+        self.add_column(
+            "created_at",
+            types::datetime().default(types::current_timestamp()),
+        );
+        self.add_column(
+            "updated_at",
+            types::datetime()
+                .default(types::current_timestamp())
+                .on_update(types::current_timestamp()),
+        );
+    }
+
+    /// Add `add_timestamps` together with `created_by` and `updated_by`
+    ///
+    /// Both of the new columns are nullable `VARCHAR(255)`, meant for
+    /// the name or id of whoever wrote a row. Postgres doesn't refresh
+    /// `updated_at` on its own; that takes a trigger, which can be
+    /// added with `Migration::inject_custom`.
+    pub fn add_audit_columns(&mut self) {
+        self.add_timestamps();
+        self.add_column("created_by", types::varchar(255).nullable(true));
+        self.add_column("updated_by", types::varchar(255).nullable(true));
+    }
+
+    /// Add the columns of a polymorphic association, which can point to rows of any table
+    ///
+    /// This adds `<name>_type`, the name of the referenced table, and
+    /// `<name>_id`, its key, as well as an index over both columns
+    /// (`<table>_<name>_idx`).
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.create_table("comments", |t| {
+    ///     t.add_column("id", types::primary());
+    ///     t.add_polymorphic("commentable");
+    /// });
+    /// ```
+    pub fn add_polymorphic(&mut self, name: &str) {
+        let kind = format!("{}_type", name);
+        let id = format!("{}_id", name);
+
+        self.add_column(kind.as_str(), types::varchar(255));
+        self.add_column(id.as_str(), types::integer());
+
+        let index = format!("{}_{}_idx", self.meta.name, name);
+        self.add_index(index, types::index(vec![kind, id]));
+    }
+
+    /// Add a named constraint spanning over multiple columns
+    ///
+    /// Constraints are created with builders like
+    /// `types::foreign_constraint`.
+    pub fn add_constraint<S: Into<String>>(&mut self, name: S, constraint: Type) {
+        match constraint.inner {
+            crate::types::BaseType::Constraint(_, _) => {}
+            _ => panic!("Calling `add_constraint` with a non-`Constraint` type is not allowed!"),
+        }
+
+        self.columns
+            .push(TableChange::AddConstraint(name.into(), constraint));
+    }
+
+    /// Make a set of columns unique, without naming the constraint
+    ///
+    /// The database chooses the name. Use `types::unique_constraint`
+    /// with `add_constraint` to name it instead.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.create_table("users", |t| {
+    ///     t.add_column("first", types::text());
+    ///     t.add_column("last", types::text());
+    ///     t.add_unique(&["first", "last"]);
+    /// });
+    /// ```
+    pub fn add_unique(&mut self, columns: &[&str]) {
+        self.columns
+            .push(TableChange::AddUnique(types::unique_constraint(columns.to_vec())));
+    }
+
     /// Inject a line of custom SQL into the table block
     ///
     /// This is a bypass to the barrel typesystem, in case there is
@@ -82,6 +400,9 @@ impl Table {
     }
 
     /// Add a new index to a table, spanning over multiple columns
+    ///
+    /// Indices are standalone statements, so on Postgres this also works
+    /// for materialized views, by passing the view name to `change_table`.
     pub fn add_index<S: Into<String>>(&mut self, name: S, columns: Type) {
         match columns.inner {
             crate::types::BaseType::Index(_) => {}
@@ -95,6 +416,31 @@ impl Table {
         });
     }
 
+    /// Add a new index to a table, only covering rows that match `conditions`
+    ///
+    /// The conditions are passed through to the `WHERE` clause of the
+    /// index as raw SQL. `Migration::validate` catches empty conditions
+    /// and unbalanced parentheses. Partial indices are not supported by
+    /// MySQL.
+    pub fn add_partial_index<S: Into<String>, C: Into<String>>(
+        &mut self,
+        name: S,
+        columns: Type,
+        conditions: C,
+    ) {
+        match columns.inner {
+            crate::types::BaseType::Index(_) => {}
+            _ => panic!("Calling `add_partial_index` with a non-`Index` type is not allowed!"),
+        }
+
+        self.indices.push(IndexChange::AddPartialIndex {
+            table: self.meta.name.clone(),
+            index: name.into(),
+            columns,
+            conditions: conditions.into(),
+        });
+    }
+
     /// Drop an index on this table
     pub fn drop_index<S: Into<String>>(&mut self, name: S) {
         self.indices.push(IndexChange::RemoveIndex(
@@ -103,6 +449,313 @@ impl Table {
         ));
     }
 
+    /// The SQL written by the user in the changes of this table
+    ///
+    /// Rendering keeps it as written, e.g. its case.
+    pub(crate) fn user_sql(&self) -> Vec<String> {
+        let mut sql = vec![];
+
+        for change in &self.columns {
+            match change {
+                TableChange::AddColumn(_, c)
+                | TableChange::AddColumnFor(_, _, c)
+                | TableChange::SetCollation(_, _, c)
+                | TableChange::AddConstraint(_, c)
+                | TableChange::AddUnique(c) => sql.extend(c.user_sql()),
+                TableChange::Rebuild(columns) => sql.extend(columns.iter().flat_map(|(_, c)| c.user_sql())),
+                TableChange::SetDefault(_, d) => sql.extend(d.user_sql()),
+                TableChange::CustomLine(line) => sql.push(line.clone()),
+                _ => {}
+            }
+        }
+
+        for change in &self.indices {
+            match change {
+                IndexChange::AddIndex { columns, .. } => sql.extend(columns.user_sql()),
+                IndexChange::AddPartialIndex { columns, conditions, .. } => {
+                    sql.extend(columns.user_sql());
+                    sql.push(conditions.clone());
+                }
+                IndexChange::RemoveIndex(_, _) => {}
+            }
+        }
+
+        sql
+    }
+
+    /// All enum types used by columns that are added for backend `T`
+    pub(crate) fn enum_types<T: SqlGenerator>(&self) -> Vec<(String, Vec<String>)> {
+        self.columns
+            .iter()
+            .filter_map(|change| match change {
+                TableChange::AddColumn(_, col) => Some(col),
+                TableChange::AddColumnFor(v, _, col) if *v == T::variant() => Some(col),
+                _ => None,
+            })
+            .filter_map(|col| {
+                // Arrays of enums need the enum type as well
+                let mut inner = &col.inner;
+                while let BaseType::Array(ref it) = inner {
+                    inner = it;
+                }
+
+                match inner {
+                    BaseType::Enum(ref name, ref variants) => Some((name.clone(), variants.clone())),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Turn inline foreign key columns into table-level constraints
+    ///
+    /// Only references with a single key are changed, as those are
+    /// the ones a constraint can express.
+    pub(crate) fn foreign_keys_as_constraints(&mut self) {
+        let mut constraints = vec![];
+
+        for change in self.columns.iter_mut() {
+            if let TableChange::AddColumn(ref name, ref mut col) = change {
+                let (schema, table, key) = match col.inner {
+                    BaseType::Foreign(ref schema, ref table, ref refs) if refs.0.len() == 1 => {
+                        (schema.clone(), table.clone(), refs.0[0].clone())
+                    }
+                    _ => continue,
+                };
+
+                let mut constraint = types::foreign_constraint(&[name], &table, &[&key], col.on_delete.clone(), None);
+                if let BaseType::Constraint(ConstraintType::Foreign { schema: ref mut s, .. }, _) = constraint.inner {
+                    *s = schema;
+                }
+
+                col.inner = BaseType::Integer;
+                constraints.push(TableChange::AddConstraint(format!("{}_{}_fkey", self.meta.name, name), constraint));
+            }
+        }
+
+        self.columns.extend(constraints);
+    }
+
+    /// The number of indices that are created or dropped
+    ///
+    /// Columns in the same index group count as one index.
+    pub(crate) fn index_changes(&self) -> usize {
+        let mut groups: Vec<&String> = vec![];
+        for change in &self.columns {
+            if let TableChange::AddColumn(_, Type { index_group: Some(group), .. }) = change {
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+        }
+
+        self.indices.len() + groups.len()
+    }
+
+    /// Move plain indices into the column list of the table
+    ///
+    /// Only done if the backend can declare them there. Partial
+    /// indices and index removals stay separate statements.
+    pub(crate) fn indices_inline<T: SqlGenerator>(&mut self) {
+        let mut rest = vec![];
+
+        for change in self.indices.drain(..) {
+            let inline = match change {
+                IndexChange::AddIndex { ref index, ref columns, .. } => T::inline_index(index, columns),
+                _ => None,
+            };
+            match inline {
+                Some(sql) => self.columns.push(TableChange::CustomLine(sql)),
+                None => rest.push(change),
+            }
+        }
+
+        self.indices = rest;
+    }
+
+    /// Remove the foreign key constraints of this table and return them
+    pub(crate) fn take_foreign_keys(&mut self) -> Vec<(String, Type)> {
+        let mut keys = vec![];
+
+        self.columns.retain(|change| match change {
+            TableChange::AddConstraint(name, c)
+                if matches!(c.inner, BaseType::Constraint(ConstraintType::Foreign { .. }, _)) =>
+            {
+                keys.push((name.clone(), c.clone()));
+                false
+            }
+            _ => true,
+        });
+
+        keys
+    }
+
+    /// Put `prefix` in front of the tables referenced by foreign keys
+    pub(crate) fn prefix_foreign_keys(&mut self, prefix: &str) {
+        for change in self.columns.iter_mut() {
+            match change {
+                TableChange::AddColumn(_, col) | TableChange::AddColumnFor(_, _, col) => {
+                    if let BaseType::Foreign(_, ref mut table, _) = col.inner {
+                        table.insert_str(0, prefix);
+                    }
+                }
+                TableChange::AddConstraint(_, c) => {
+                    if let BaseType::Constraint(ConstraintType::Foreign { ref mut table, .. }, _) =
+                        c.inner
+                    {
+                        table.insert_str(0, prefix);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Check this table for mistakes that would result in invalid SQL
+    pub fn validate<T: SqlGenerator>(&self) -> Result<(), ValidationError> {
+        let names = self.columns.iter().filter_map(|change| match change {
+            TableChange::AddColumn(name, _)
+            | TableChange::AddColumnFor(_, name, _)
+            | TableChange::RenameColumn(_, name)
+            | TableChange::AddConstraint(name, _) => Some(name),
+            _ => None,
+        });
+        let indices = self.indices.iter().filter_map(|change| match change {
+            IndexChange::AddIndex { index, .. } | IndexChange::AddPartialIndex { index, .. } => {
+                Some(index)
+            }
+            _ => None,
+        });
+        for name in Some(&self.meta.name).into_iter().chain(names).chain(indices) {
+            validate_identifier::<T>(name)?;
+        }
+
+        for change in &self.columns {
+            let (name, columns, refs) = match change {
+                TableChange::AddColumn(name, col) => match col.inner {
+                    BaseType::Foreign(_, _, ref refs) => (name, 1, refs.0.len()),
+                    _ => continue,
+                },
+                TableChange::AddConstraint(name, c) => match c.inner {
+                    BaseType::Constraint(ConstraintType::Foreign { ref refs, .. }, ref cols) => {
+                        (name, cols.len(), refs.len())
+                    }
+                    _ => continue,
+                },
+                _ => continue,
+            };
+
+            if columns != refs {
+                return Err(ValidationError::ForeignKeyColumnMismatch {
+                    table: self.meta.name(),
+                    name: name.clone(),
+                    columns,
+                    refs,
+                });
+            }
+        }
+
+        for change in &self.indices {
+            if let IndexChange::AddPartialIndex { index, conditions, .. } = change {
+                if !is_valid_predicate(conditions) {
+                    return Err(ValidationError::InvalidPredicate {
+                        index: index.clone(),
+                        predicate: conditions.clone(),
+                    });
+                }
+            }
+        }
+
+        #[cfg(feature = "mysql")]
+        {
+            if T::variant() == SqlVariant::Mysql {
+                self.validate_mysql_text_indices()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Columns added to an existing table need a value for the existing rows
+    ///
+    /// Nullable, defaulted, auto-incrementing and generated columns
+    /// get one from the database, anything else needs a default.
+    pub(crate) fn validate_added_columns(&self) -> Result<(), ValidationError> {
+        for change in &self.columns {
+            match change {
+                TableChange::AddColumn(name, col) | TableChange::AddColumnFor(_, name, col) => {
+                    let filled = col.nullable
+                        || col.default.is_some()
+                        || col.increments
+                        || col.generated.is_some()
+                        || col.identity.is_some()
+                        || col.inner == BaseType::Primary;
+                    if !filled {
+                        return Err(ValidationError::NotNullWithoutDefault {
+                            table: self.meta.name(),
+                            column: name.clone(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// MySQL can only index `TEXT` columns with a prefix length
+    #[cfg(feature = "mysql")]
+    fn validate_mysql_text_indices(&self) -> Result<(), ValidationError> {
+        let mut text = vec![];
+        for change in &self.columns {
+            match change {
+                TableChange::AddColumn(name, col)
+                | TableChange::AddColumnFor(SqlVariant::Mysql, name, col)
+                    if col.inner == BaseType::Text =>
+                {
+                    if col.indexed || col.unique {
+                        return Err(self.text_indexed(name));
+                    }
+                    text.push(name);
+                }
+                _ => {}
+            }
+        }
+
+        let unique = self.columns.iter().filter_map(|change| match change {
+            TableChange::AddConstraint(_, c) | TableChange::AddUnique(c) => match c.inner {
+                BaseType::Constraint(ConstraintType::Unique(ref cols), _) => Some(cols),
+                _ => None,
+            },
+            _ => None,
+        });
+        let indexed = self.indices.iter().filter_map(|change| match change {
+            IndexChange::AddIndex { columns, .. }
+            | IndexChange::AddPartialIndex { columns, .. } => match columns.inner {
+                BaseType::Index(ref cols) => Some(cols),
+                _ => None,
+            },
+            _ => None,
+        });
+
+        for col in unique.chain(indexed).flatten() {
+            if text.contains(&&col.name) {
+                return Err(self.text_indexed(&col.name));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "mysql")]
+    fn text_indexed(&self, column: &str) -> ValidationError {
+        ValidationError::TextColumnIndexed {
+            table: self.meta.name(),
+            column: column.into(),
+        }
+    }
+
     /// Generate Sql for this table, returned as two vectors
     ///
     /// The first vector (`.0`) represents all column changes done to the table,
@@ -117,19 +770,90 @@ impl Table {
     ) -> (Vec<String>, Vec<String>) {
         use IndexChange as IC;
         use TableChange as TC;
+        let strict = self.meta.strict;
 
-        let columns = self
+        // Columns for other backends are skipped, the rest are regular columns
+        let mut changes: Vec<TableChange> = self
             .columns
+            .iter()
+            .filter_map(|change| match change {
+                TC::AddColumnFor(v, name, col) if *v == T::variant() => {
+                    Some(TC::AddColumn(name.clone(), col.clone()))
+                }
+                TC::AddColumnFor(_, _, _) | TC::Rebuild(_) => None,
+                _ => Some(change.clone()),
+            })
+            .collect();
+
+        // Only one primary key is allowed, so multiple primary columns become a composite key
+        let primary: Vec<String> = changes
+            .iter()
+            .filter_map(|change| match change {
+                TC::AddColumn(name, col) if col.primary => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        if primary.len() > 1 {
+            for change in changes.iter_mut() {
+                if let TC::AddColumn(_, ref mut col) = change {
+                    col.primary = false;
+                }
+            }
+        }
+
+        // Rebuilds replace the whole table, so they are standalone statements
+        let table = self.meta.name();
+        let mut statements = vec![];
+        for change in &self.columns {
+            if let TC::Rebuild(columns) = change {
+                let tmp = format!("{}_new", table);
+                let names: Vec<String> = columns.iter().map(|(n, _)| n.clone()).collect();
+                let defs: Vec<String> = columns
+                    .iter()
+                    .map(|(n, c)| T::add_column(false, schema, n, c))
+                    .collect();
+
+                statements.push(format!("{} ({})", T::create_table(&tmp, schema), defs.join(", ")));
+                statements.push(T::copy_rows(&table, &tmp, &names, schema));
+                statements.push(T::drop_table(&table, schema));
+                statements.push(T::rename_table(&tmp, &table, schema));
+            }
+        }
+
+        let mut columns: Vec<String> = changes
             .iter_mut()
             .map(|change| match change {
+                // STRICT tables (only Sqlite has them) only know a few types,
+                // booleans are stored as a constrained `INTEGER`
+                &mut TC::AddColumn(ref name, ref col) if strict => {
+                    let sql = T::add_column(ex, schema, name, &strict_column(col));
+                    match col.inner {
+                        BaseType::Boolean => format!("{} CHECK ({} IN (0, 1))", sql, quote_identifier::<T>(name)),
+                        _ => sql,
+                    }
+                }
                 &mut TC::AddColumn(ref name, ref col) => T::add_column(ex, schema, name, &col),
+                &mut TC::AddColumnFor(_, _, _) | &mut TC::Rebuild(_) => unreachable!(),
                 &mut TC::DropColumn(ref name) => T::drop_column(name),
+                &mut TC::DropPrimaryKey => T::drop_primary_key(&self.meta.name),
+                &mut TC::AddIdentity(ref name, mode) => T::add_identity(name, mode),
+                &mut TC::DropIdentity(ref name) => T::drop_identity(name),
+                &mut TC::SetDefault(ref name, ref default) => T::set_default(name, default),
+                &mut TC::SetCollation(ref name, ref collation, ref c) => {
+                    T::set_collation(name, collation, c, schema)
+                }
                 &mut TC::RenameColumn(ref old, ref new) => T::rename_column(old, new),
                 &mut TC::ChangeColumn(ref mut name, _, _) => T::alter_table(name, schema),
+                &mut TC::AddConstraint(ref name, ref c) => T::add_constraint(ex, schema, name, c),
+                &mut TC::AddUnique(ref c) => T::add_unique(ex, schema, c),
                 &mut TC::CustomLine(ref sql) => sql.clone(),
             })
             .collect();
 
+        if primary.len() > 1 {
+            columns.push(T::primary_key(ex, &primary));
+        }
+
         let indeces = self
             .indices
             .iter()
@@ -139,19 +863,128 @@ impl Table {
                     table,
                     columns,
                 } => T::create_index(table, schema, index, columns),
-                IC::RemoveIndex(_, index) => T::drop_index(index),
-            })
-            .collect();
+                IC::AddPartialIndex {
+                    index,
+                    table,
+                    columns,
+                    conditions,
+                } => T::create_partial_index(table, schema, index, columns, conditions),
+                IC::RemoveIndex(table, index) => T::drop_index(table, schema, index),
+            });
+        statements.extend(indeces);
+
+        // Columns in the same index group share one index, in column order
+        let mut groups: Vec<(&String, Vec<&str>)> = vec![];
+        for change in &changes {
+            if let TC::AddColumn(name, Type { index_group: Some(group), .. }) = change {
+                match groups.iter_mut().find(|(g, _)| *g == group) {
+                    Some((_, cols)) => cols.push(name),
+                    None => groups.push((group, vec![name.as_str()])),
+                }
+            }
+        }
+        statements.extend(groups.into_iter().map(|(group, cols)| {
+            T::create_index(&self.meta.name, schema, group, &types::index(cols))
+        }));
 
-        (columns, indeces)
+        // Index comments can only be added once the index exists
+        statements.extend(self.indices.iter().filter_map(|change| match change {
+            IC::AddIndex { index, columns: Type { comment: Some(c), .. }, .. }
+            | IC::AddPartialIndex { index, columns: Type { comment: Some(c), .. }, .. } => {
+                T::comment_on_index(schema, index, c)
+            }
+            _ => None,
+        }));
+
+        // Constraint comments and column storage are separate statements after the table
+        statements.extend(changes.iter().filter_map(|change| match change {
+            TC::AddConstraint(name, Type { comment: Some(c), .. }) => {
+                T::comment_on_constraint(&table, schema, name, c)
+            }
+            TC::AddColumn(name, Type { storage: Some(s), .. }) => {
+                T::set_column_storage(&table, schema, name, s)
+            }
+            _ => None,
+        }));
+
+        (columns, statements)
     }
 }
 
+/// Map a column to the types a `STRICT` table accepts
+///
+/// Those are `INTEGER`, `REAL`, `TEXT`, `BLOB` and `ANY`. Custom
+/// types are kept as they are.
+fn strict_column(col: &Type) -> Type {
+    let inner = match col.inner {
+        BaseType::Boolean => BaseType::Integer,
+        BaseType::Varchar(_)
+        | BaseType::Date
+        | BaseType::DateTime
+        | BaseType::DateTimeTz
+        | BaseType::Time => BaseType::Text,
+        BaseType::Decimal(_, _) => BaseType::Double,
+        BaseType::Array(_) => panic!("STRICT tables do not support arrays!"),
+        ref t => t.clone(),
+    };
+    let default = match col.default {
+        Some(WrappedDefault::Boolean(b)) => Some(WrappedDefault::Integer(b as i64)),
+        ref d => d.clone(),
+    };
+
+    Type { inner, default, ..col.clone() }
+}
+
+/// Quote an identifier for backend `T`, doubling quotes inside of it
+fn quote_identifier<T: SqlGenerator>(name: &str) -> String {
+    let quote = T::identifier_quote();
+    format!("{}{}{}", quote, name.replace(quote, &format!("{}{}", quote, quote)), quote)
+}
+
+/// Check that an identifier isn't silently truncated by the backend
+pub(crate) fn validate_identifier<T: SqlGenerator>(name: &str) -> Result<(), ValidationError> {
+    match T::identifier_limit(name) {
+        Some(max) => Err(ValidationError::IdentifierTooLong {
+            name: name.into(),
+            max,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check that a predicate isn't empty and its parentheses are balanced
+///
+/// Parentheses inside of strings and quoted identifiers don't count.
+fn is_valid_predicate(predicate: &str) -> bool {
+    let mut depth = 0;
+    let mut quote = None;
+
+    for c in predicate.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' || c == '`' => quote = Some(c),
+            None if c == '(' => depth += 1,
+            None if c == ')' => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            None => {}
+        }
+    }
+
+    !predicate.trim().is_empty() && depth == 0 && quote.is_none()
+}
+
 /// Some metadata about a table that was just created
 #[derive(Debug, Clone)]
 pub struct TableMeta {
     pub name: String,
     pub encoding: String,
+    pub strict: bool,
+    pub storage_params: Vec<(String, String)>,
 }
 
 impl TableMeta {
@@ -160,6 +993,8 @@ impl TableMeta {
         Self {
             name,
             encoding: "utf-8".to_owned(),
+            strict: false,
+            storage_params: vec![],
         }
     }
 
@@ -174,4 +1009,25 @@ impl TableMeta {
         self.encoding = enc.into();
         self
     }
+
+    /// Make this a `STRICT` table which enforces its column types
+    ///
+    /// This is only supported by Sqlite. Strict tables only know
+    /// `INTEGER`, `REAL`, `TEXT`, `BLOB` and `ANY`, so varchars, dates
+    /// and times are rendered as `TEXT` and decimals as `REAL`. Boolean
+    /// columns become `INTEGER` with a check that only allows `0` and
+    /// `1`, and their defaults are rendered as `0` or `1` too.
+    pub fn strict(&mut self, arg: bool) -> &mut TableMeta {
+        self.strict = arg;
+        self
+    }
+
+    /// Add a storage parameter to the `WITH (...)` clause of this table
+    ///
+    /// Values are passed through as raw SQL, so legacy options like
+    /// `OIDS=FALSE` work as well. This is only supported by Postgres.
+    pub fn storage_param<S: Into<String>>(&mut self, key: S, value: S) -> &mut TableMeta {
+        self.storage_params.push((key.into(), value.into()));
+        self
+    }
 }
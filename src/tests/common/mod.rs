@@ -5,8 +5,8 @@ mod utils;
 #[cfg(all(feature = "sqlite3", feature = "pg", feature = "mysql"))]
 mod runtime;
 
-use crate::types::{BaseType, Type, WrappedDefault};
-use crate::Migration;
+use crate::types::{self, BaseType, Type, WrappedDefault};
+use crate::{Migration, Table};
 
 #[test]
 fn create_multiple_tables() {
@@ -25,6 +25,18 @@ fn create_table_if_not_exists() {
     assert!(migr.changes.len() == 1);
 }
 
+#[test]
+fn column_names() {
+    let mut t = Table::new("users");
+    t.add_column("id", types::primary());
+    t.add_column("name", types::text());
+    t.drop_column("legacy");
+    t.add_column("email", types::varchar(255));
+    t.add_column("created_at", types::datetime());
+
+    assert_eq!(t.column_names(), vec!["id", "name", "email", "created_at"]);
+}
+
 #[test]
 fn pin_public_api() {
     // The best sql type because it's very queer 🏳️‍🌈
@@ -49,6 +61,20 @@ fn pin_struct_layout() {
         unique: false,
         increments: false,
         default: None,
+        on_update: None,
+        not_null_name: None,
+        length_semantics: None,
+        comment: None,
+        compression: None,
+        storage: None,
+        generated: None,
+        index_group: None,
+        index_method: None,
+        invisible: false,
+        collation: None,
+        identity: None,
+        srid: None,
+        on_delete: None,
         size: None,
         inner: BaseType::Custom("GAY"),
     };
@@ -59,6 +85,20 @@ fn pin_struct_layout() {
     assert_eq!(tt.unique, false);
     assert_eq!(tt.increments, false);
     assert_eq!(tt.default, None);
+    assert_eq!(tt.on_update, None);
+    assert_eq!(tt.not_null_name, None);
+    assert_eq!(tt.length_semantics, None);
+    assert_eq!(tt.comment, None);
+    assert_eq!(tt.compression, None);
+    assert_eq!(tt.storage, None);
+    assert_eq!(tt.generated, None);
+    assert_eq!(tt.index_group, None);
+    assert_eq!(tt.index_method, None);
+    assert!(!tt.invisible);
+    assert_eq!(tt.collation, None);
+    assert_eq!(tt.identity, None);
+    assert_eq!(tt.srid, None);
+    assert_eq!(tt.on_delete, None);
     assert_eq!(tt.size, None);
     assert_eq!(tt.inner, BaseType::Custom("GAY"));
 }
@@ -105,6 +145,21 @@ fn default_render_uuid() {
 //     assert_eq!(format!("{}", Date(SystemTime::now())), "".to_owned());
 // }
 
+#[test]
+fn default_render_date_iso() {
+    use self::WrappedDefault::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let time = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    assert_eq!(format!("{}", Date(time)), "2020-09-13 12:26:40".to_owned());
+
+    let time = UNIX_EPOCH + Duration::from_micros(951_782_400_000_250);
+    assert_eq!(format!("{}", Date(time)), "2000-02-29 00:00:00.000250".to_owned());
+
+    let time = UNIX_EPOCH - Duration::from_secs(1);
+    assert_eq!(format!("{}", Date(time)), "1969-12-31 23:59:59".to_owned());
+}
+
 #[test]
 fn default_render_binary() {
     use self::WrappedDefault::*;
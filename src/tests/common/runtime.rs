@@ -17,3 +17,21 @@ fn generate_from() {
     let _ = m.make_from(SqlVariant::Mysql);
     let _ = m.make_from(SqlVariant::Sqlite);
 }
+
+#[test]
+fn add_column_for_backend() {
+    let mut m = Migration::new();
+    m.create_table("users", |table| {
+        table.add_column("name", types::text());
+        table.add_column_for(SqlVariant::Pg, "tags", types::array(&types::text()));
+    });
+
+    assert_eq!(
+        m.make_from(SqlVariant::Pg),
+        "CREATE TABLE \"users\" (\"name\" TEXT NOT NULL, \"tags\" TEXT[] NOT NULL);"
+    );
+    assert_eq!(
+        m.make_from(SqlVariant::Sqlite),
+        "CREATE TABLE \"users\" (\"name\" TEXT NOT NULL);"
+    );
+}
@@ -55,11 +55,122 @@ fn date() {
     assert_eq!(String::from("ADD COLUMN `Date` DATE NOT NULL"), sql);
 }
 
+#[test]
+fn not_null_named() {
+    let sql = MySql::add_column(true, None, "Integer", &types::integer().not_null_named("integer_nn"));
+    assert_eq!(String::from("ADD COLUMN `Integer` INTEGER NOT NULL"), sql);
+}
+
 #[test]
 fn foreign() {
     let sql = MySql::add_column(true, None, "Foreign", &types::foreign("posts", "id"));
     assert_eq!(
-        String::from("ADD COLUMN `Foreign` INTEGER REFERENCES posts(id) NOT NULL"),
+        String::from("ADD COLUMN `Foreign` INTEGER REFERENCES `posts`(`id`) NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn varchar_length_semantics() {
+    use crate::types::LengthSemantics;
+
+    let sql = MySql::add_column(
+        true,
+        None,
+        "Name",
+        &types::varchar(64).length_semantics(LengthSemantics::Char),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN `Name` VARCHAR(64) CHARACTER SET utf8mb4 NOT NULL"),
+        sql
+    );
+
+    let sql = MySql::add_column(
+        true,
+        None,
+        "Code",
+        &types::varchar(64).length_semantics(LengthSemantics::Byte),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN `Code` VARCHAR(64) CHARACTER SET latin1 NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn integer_sized() {
+    let sizes = [(1, "TINYINT"), (2, "SMALLINT"), (4, "INT"), (8, "BIGINT")];
+    for (bytes, name) in sizes.iter() {
+        let sql = MySql::add_column(true, None, "Count", &types::integer_sized(*bytes));
+        assert_eq!(format!("ADD COLUMN `Count` {} NOT NULL", name), sql);
+    }
+}
+
+#[test]
+fn enum_default() {
+    let sql = MySql::add_column(
+        true,
+        None,
+        "Status",
+        &types::enumeration("status", &["active", "inactive"]).default("active"),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN `Status` ENUM('active', 'inactive') DEFAULT 'active' NOT NULL"),
         sql
     );
 }
+
+#[test]
+fn time_defaults() {
+    let sql = MySql::add_column(true, None, "At", &types::time().default(types::current_time()));
+    assert_eq!(String::from("ADD COLUMN `At` TIME DEFAULT (CURRENT_TIME) NOT NULL"), sql);
+
+    let sql = MySql::add_column(true, None, "On", &types::datetime().default(types::current_timestamp()));
+    assert_eq!(String::from("ADD COLUMN `On` DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL"), sql);
+}
+
+#[test]
+fn binary_collation() {
+    let sql = MySql::add_column(true, None, "Email", &types::varchar(255).unique(true).collation("utf8mb4_bin"));
+    assert_eq!(String::from("ADD COLUMN `Email` VARCHAR(255) COLLATE utf8mb4_bin NOT NULL UNIQUE"), sql);
+}
+
+#[test]
+fn invisible() {
+    let sql = MySql::add_column(true, None, "Secret", &types::text().nullable(true).invisible(true));
+    assert_eq!(String::from("ADD COLUMN `Secret` TEXT INVISIBLE"), sql);
+}
+
+#[test]
+fn uuid_fallback() {
+    let sql = MySql::add_column(true, None, "Id", &types::uuid());
+    assert_eq!(String::from("ADD COLUMN `Id` CHAR(36) NOT NULL UNIQUE"), sql);
+
+    let sql = MySql::add_column(true, None, "Id", &types::uuid().default(types::random_uuid()));
+    assert_eq!(String::from("ADD COLUMN `Id` CHAR(36) DEFAULT (UUID()) NOT NULL UNIQUE"), sql);
+}
+
+#[test]
+fn currency() {
+    let sql = MySql::add_column(true, None, "Price", &types::currency());
+    assert_eq!(String::from("ADD COLUMN `Price` DECIMAL(19, 4) NOT NULL"), sql);
+}
+
+#[test]
+fn decimal_default() {
+    let sql = MySql::add_column(true, None, "Price", &types::decimal(10, 2).default(10.5));
+    assert_eq!(String::from("ADD COLUMN `Price` DECIMAL(10, 2) DEFAULT 10.5 NOT NULL"), sql);
+
+    // Large values are written out, not in scientific notation
+    let sql = MySql::add_column(true, None, "Price", &types::decimal(30, 2).default(1e20));
+    assert_eq!(String::from("ADD COLUMN `Price` DECIMAL(30, 2) DEFAULT 100000000000000000000 NOT NULL"), sql);
+}
+
+#[test]
+fn geometry_srid() {
+    let sql = MySql::add_column(true, None, "Location", &types::point().srid(4326));
+    assert_eq!(String::from("ADD COLUMN `Location` POINT SRID 4326 NOT NULL"), sql);
+
+    let sql = MySql::add_column(true, None, "Shape", &types::geometry().nullable(true));
+    assert_eq!(String::from("ADD COLUMN `Shape` GEOMETRY"), sql);
+}
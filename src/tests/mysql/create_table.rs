@@ -35,3 +35,195 @@ fn create_table_if_not_exists_doesnt_hit_unreachable() {
     });
     assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `artist` IF NOT EXISTS (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, `name` TEXT, `description` TEXT, `pic` TEXT, `mbid` TEXT);"));
 }
+
+#[test]
+fn timestamps() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("id", types::primary());
+        t.add_timestamps();
+    });
+    assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `posts` (`id` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, `created_at` DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL, `updated_at` DATETIME DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP NOT NULL);"));
+}
+
+#[test]
+fn anonymous_unique() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("first", types::varchar(64));
+        t.add_column("last", types::varchar(64));
+        t.add_unique(&["first", "last"]);
+    });
+    m.change_table("posts", |t| t.add_unique(&["slug"]));
+    assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `users` (`first` VARCHAR(64) NOT NULL, `last` VARCHAR(64) NOT NULL, UNIQUE (`first`, `last`));ALTER TABLE `posts` ADD UNIQUE (`slug`);"));
+}
+
+#[test]
+fn json_array_check() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("tags", types::json());
+        t.add_constraint("posts_tags_check", types::json_array_check("tags"));
+    });
+    assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `posts` (`tags` JSON NOT NULL, CONSTRAINT `posts_tags_check` CHECK (JSON_TYPE(`tags`) = 'ARRAY'));"));
+}
+
+#[test]
+fn backtick_quoting() {
+    let mut m = Migration::new();
+    m.create_table("order", |t| {
+        t.add_column("key", types::primary());
+        t.add_column("group", types::foreign("groups", "select"));
+    });
+    assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `order` (`key` INTEGER NOT NULL AUTO_INCREMENT PRIMARY KEY, `group` INTEGER REFERENCES `groups`(`select`) NOT NULL);"));
+}
+
+#[test]
+fn validate_text_index() {
+    use crate::ValidationError;
+
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("email", types::text().unique(true));
+    });
+    assert_eq!(
+        m.validate::<MySql>(),
+        Err(ValidationError::TextColumnIndexed {
+            table: "users".into(),
+            column: "email".into(),
+        })
+    );
+
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("name", types::text());
+        t.add_index("users_name_idx", types::index(vec!["name"]));
+    });
+    assert!(m.validate::<MySql>().is_err());
+
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("email", types::varchar(255).unique(true));
+        t.add_column("bio", types::text());
+    });
+    assert_eq!(m.validate::<MySql>(), Ok(()));
+}
+
+#[test]
+fn create_table_like() {
+    let mut m = Migration::new();
+    m.create_table_like("users_copy", "users", &["ALL"]);
+    assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `users_copy` LIKE `users`;"));
+}
+
+#[test]
+fn foreign_constraint_index_name() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("author_id", types::integer());
+        t.add_constraint(
+            "posts_author_fkey",
+            types::foreign_constraint(&["author_id"], "users", &["id"], None, None)
+                .index_name("posts_author_idx"),
+        );
+    });
+    assert_eq!(m.make::<MySql>(), String::from("CREATE TABLE `posts` (`author_id` INTEGER NOT NULL, CONSTRAINT `posts_author_fkey` FOREIGN KEY `posts_author_idx` (`author_id`) REFERENCES `users`(`id`));"));
+}
+
+#[test]
+fn validate_identifier_length() {
+    use crate::ValidationError;
+
+    let name = "a".repeat(65);
+    let mut m = Migration::new();
+    m.create_table(name.clone(), |t| {
+        t.add_column("id", types::primary());
+    });
+    assert_eq!(
+        m.validate::<MySql>(),
+        Err(ValidationError::IdentifierTooLong { name, max: 64 })
+    );
+
+    let mut m = Migration::new();
+    m.create_table("a".repeat(64), |t| {
+        t.add_column("id", types::primary());
+    });
+    assert_eq!(m.validate::<MySql>(), Ok(()));
+}
+
+#[test]
+fn create_or_replace_view() {
+    let mut m = Migration::new();
+    m.create_view("admins", "SELECT * FROM users WHERE admin")
+        .or_replace(true);
+    m.drop_view("admins");
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE OR REPLACE VIEW `admins` AS SELECT * FROM users WHERE admin;DROP VIEW `admins`;")
+    );
+}
+
+#[test]
+fn add_generated_column() {
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.add_column("full_name", types::varchar(255).generated("CONCAT(`first`, ' ', `last`)"));
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("ALTER TABLE `users` ADD COLUMN `full_name` VARCHAR(255) GENERATED ALWAYS AS (CONCAT(`first`, ' ', `last`)) STORED NOT NULL;")
+    );
+}
+
+#[test]
+fn create_table_inline_indices() {
+    let mut m = Migration::new().inline_indices(true);
+    m.create_table("users", |t| {
+        t.add_column("name", types::varchar(255));
+        t.add_column("email", types::varchar(255));
+        t.add_index("users_name_idx", types::index(vec!["name"]));
+        t.add_index("users_email_idx", types::index(vec!["email"]).unique(true));
+    });
+
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("CREATE TABLE `users` (`name` VARCHAR(255) NOT NULL, `email` VARCHAR(255) NOT NULL, INDEX `users_name_idx` (`name`), UNIQUE INDEX `users_email_idx` (`email`));")
+    );
+}
+
+#[test]
+fn insert_rows_booleans_and_dates() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut m = Migration::new();
+    m.insert_rows(
+        "users",
+        &["admin", "joined"],
+        vec![
+            vec![true.into(), (UNIX_EPOCH + Duration::from_secs(1_600_000_000)).into()],
+            vec![false.into(), UNIX_EPOCH.into()],
+        ],
+    );
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("INSERT INTO `users` (`admin`, `joined`) VALUES (TRUE, '2020-09-13 12:26:40'), (FALSE, '1970-01-01 00:00:00');")
+    );
+}
+
+#[test]
+fn insert_rows_backslashes() {
+    let mut m = Migration::new();
+    m.insert_rows("paths", &["path"], vec![vec!["C:\\new".into()], vec!["it's\\".into()]]);
+    assert_eq!(
+        m.make::<MySql>(),
+        String::from("INSERT INTO `paths` (`path`) VALUES ('C:\\\\new'), ('it''s\\\\');")
+    );
+}
+
+#[test]
+fn enum_backslashes() {
+    let sql = MySql::add_column(false, None, "dir", &types::enumeration("dirs", &["a\\b"]));
+    assert_eq!(sql, String::from("`dir` ENUM('a\\\\b') NOT NULL"));
+}
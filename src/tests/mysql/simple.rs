@@ -48,3 +48,15 @@ fn alter_table() {
     let sql = MySql::alter_table("table_to_alter", None);
     assert_eq!(String::from("ALTER TABLE `table_to_alter`"), sql);
 }
+
+#[test]
+fn move_table_to_schema() {
+    let sql = MySql::move_table_to_schema("users", Some("staging"), "public");
+    assert_eq!(String::from("RENAME TABLE `staging`.`users` TO `public`.`users`"), sql);
+}
+
+#[test]
+fn drop_primary_key() {
+    let sql = MySql::drop_primary_key("users");
+    assert_eq!(String::from("DROP PRIMARY KEY"), sql);
+}
@@ -2,7 +2,7 @@
 #![allow(unused_imports)]
 
 use crate::backend::{Pg, SqlGenerator};
-use crate::types;
+use crate::types::{self, Identity, WrappedDefault};
 
 #[test]
 fn text() {
@@ -58,6 +58,35 @@ fn date() {
     assert_eq!(String::from("ADD COLUMN \"Date\" DATE NOT NULL"), sql);
 }
 
+#[test]
+fn not_null_named() {
+    let sql = Pg::add_column(true, None, "Integer", &types::integer().not_null_named("integer_nn"));
+    assert_eq!(
+        String::from("ADD COLUMN \"Integer\" INTEGER CONSTRAINT \"integer_nn\" NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn default_cast() {
+    let sql = Pg::add_column(
+        true,
+        None,
+        "Json",
+        &types::json().default(WrappedDefault::AnyText("{}").cast("jsonb")),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN \"Json\" JSON DEFAULT '{}'::jsonb NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn datetime() {
+    let sql = Pg::add_column(true, None, "DateTime", &types::datetime());
+    assert_eq!(String::from("ADD COLUMN \"DateTime\" TIMESTAMP NOT NULL"), sql);
+}
+
 #[test]
 fn foreign() {
     let sql = Pg::add_column(true, None, "Foreign", &types::foreign("posts", "id"));
@@ -67,6 +96,15 @@ fn foreign() {
     );
 }
 
+#[test]
+fn nullable_foreign() {
+    let sql = Pg::add_column(true, None, "Author", &types::nullable_foreign("users", "id"));
+    assert_eq!(
+        String::from("ADD COLUMN \"Author\" INTEGER REFERENCES \"users\"(id) ON DELETE SET NULL"),
+        sql
+    );
+}
+
 #[test]
 fn custom() {
     let sql = Pg::add_column(true, None, "Point", &types::custom("POINT"));
@@ -82,6 +120,38 @@ fn array_text() {
     );
 }
 
+#[test]
+fn nextval_default() {
+    let sql = Pg::add_column(true, None, "Number", &types::integer().default(types::nextval("invoice_numbers")));
+    assert_eq!(
+        String::from("ADD COLUMN \"Number\" INTEGER DEFAULT nextval('invoice_numbers') NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn nextval_default_escaped() {
+    let sql = Pg::add_column(true, None, "Number", &types::integer().default(types::nextval("o'brien_seq")));
+    assert_eq!(
+        String::from("ADD COLUMN \"Number\" INTEGER DEFAULT nextval('o''brien_seq') NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn array_integer_default() {
+    let sql = Pg::add_column(
+        true,
+        None,
+        "Numbers",
+        &types::array(&types::integer()).default(types::array_default(vec![1, 2, 3])),
+    );
+    assert_eq!(
+        String::from("ADD COLUMN \"Numbers\" INTEGER[] DEFAULT '{1,2,3}' NOT NULL"),
+        sql
+    );
+}
+
 #[test]
 fn array_varchar() {
     let sql = Pg::add_column(
@@ -163,3 +233,105 @@ fn array_array_integer() {
         sql
     );
 }
+
+#[test]
+fn pg_lsn() {
+    let sql = Pg::add_column(true, None, "Lsn", &types::pg_lsn());
+    assert_eq!(String::from("ADD COLUMN \"Lsn\" PG_LSN NOT NULL"), sql);
+}
+
+#[test]
+fn tsquery() {
+    let sql = Pg::add_column(true, None, "Query", &types::tsquery());
+    assert_eq!(String::from("ADD COLUMN \"Query\" TSQUERY NOT NULL"), sql);
+}
+
+#[test]
+fn tsvector_generated() {
+    let sql = Pg::add_column(true, None, "Search", &types::tsvector_generated("simple", &["body"]));
+    assert_eq!(
+        String::from("ADD COLUMN \"Search\" TSVECTOR GENERATED ALWAYS AS (to_tsvector('simple', \"body\")) STORED NOT NULL"),
+        sql
+    );
+
+    let sql = Pg::add_column(true, None, "Search", &types::tsvector_generated("english", &["title", "body"]));
+    assert_eq!(
+        String::from("ADD COLUMN \"Search\" TSVECTOR GENERATED ALWAYS AS (to_tsvector('english', coalesce(\"title\", '') || ' ' || coalesce(\"body\", ''))) STORED NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn integer_sized() {
+    let sizes = [(1, "SMALLINT"), (2, "SMALLINT"), (4, "INTEGER"), (8, "BIGINT")];
+    for (bytes, name) in sizes.iter() {
+        let sql = Pg::add_column(true, None, "Count", &types::integer_sized(*bytes));
+        assert_eq!(format!("ADD COLUMN \"Count\" {} NOT NULL", name), sql);
+    }
+}
+
+#[test]
+fn time_defaults() {
+    let sql = Pg::add_column(true, None, "At", &types::time().default(types::current_time()));
+    assert_eq!(String::from("ADD COLUMN \"At\" TIME DEFAULT CURRENT_TIME NOT NULL"), sql);
+
+    let sql = Pg::add_column(true, None, "On", &types::datetime().default(types::current_timestamp()));
+    assert_eq!(String::from("ADD COLUMN \"On\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL"), sql);
+}
+
+#[test]
+fn identity() {
+    let sql = Pg::add_column(true, None, "Id", &types::identity(Identity::default()));
+    assert_eq!(String::from("ADD COLUMN \"Id\" INTEGER GENERATED BY DEFAULT AS IDENTITY NOT NULL"), sql);
+
+    let sql = Pg::add_column(true, None, "Id", &types::identity(Identity::Always).primary(true));
+    assert_eq!(
+        String::from("ADD COLUMN \"Id\" INTEGER GENERATED ALWAYS AS IDENTITY PRIMARY KEY NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn enum_default_cast() {
+    let status = || types::enumeration("status", &["active", "inactive"]);
+
+    let sql = Pg::add_column(true, None, "Status", &status().default("active"));
+    assert_eq!(String::from("ADD COLUMN \"Status\" \"status\" DEFAULT 'active'::\"status\" NOT NULL"), sql);
+
+    // A cast that was given explicitly isn't added twice
+    let sql = Pg::add_column(
+        true,
+        None,
+        "Status",
+        &status().default(WrappedDefault::AnyText("active").cast("status")),
+    );
+    assert_eq!(String::from("ADD COLUMN \"Status\" \"status\" DEFAULT 'active'::status NOT NULL"), sql);
+}
+
+#[test]
+fn datetime_tz_defaults() {
+    let sql = Pg::add_column(true, None, "On", &types::datetime_tz().default(types::current_timestamp()));
+    assert_eq!(String::from("ADD COLUMN \"On\" TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL"), sql);
+
+    let sql = Pg::add_column(true, None, "On", &types::datetime_tz().default("2020-01-01 00:00:00+00"));
+    assert_eq!(
+        String::from("ADD COLUMN \"On\" TIMESTAMPTZ DEFAULT '2020-01-01 00:00:00+00' NOT NULL"),
+        sql
+    );
+}
+
+#[test]
+fn currency() {
+    let sql = Pg::add_column(true, None, "Price", &types::currency());
+    assert_eq!(String::from("ADD COLUMN \"Price\" NUMERIC(19, 4) NOT NULL"), sql);
+}
+
+#[test]
+fn decimal_default() {
+    let sql = Pg::add_column(true, None, "Price", &types::decimal(10, 2).default(10.5));
+    assert_eq!(String::from("ADD COLUMN \"Price\" NUMERIC(10, 2) DEFAULT 10.5 NOT NULL"), sql);
+
+    // Large values are written out, not in scientific notation
+    let sql = Pg::add_column(true, None, "Price", &types::decimal(30, 2).default(1e20));
+    assert_eq!(String::from("ADD COLUMN \"Price\" NUMERIC(30, 2) DEFAULT 100000000000000000000 NOT NULL"), sql);
+}
@@ -0,0 +1,347 @@
+//! Table-level constraints
+#![allow(unused_imports)]
+
+use crate::backend::{Pg, SqlGenerator};
+use crate::types::{self, IndexColumn, ReferentialAction};
+use crate::{FkStyle, Migration, Table, ValidationError};
+
+#[test]
+fn foreign_constraint() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("author_name", types::text());
+        t.add_column("author_birthday", types::date());
+        t.add_constraint(
+            "posts_author_fkey",
+            types::foreign_constraint(
+                &["author_name", "author_birthday"],
+                "users",
+                &["name", "birthday"],
+                Some(ReferentialAction::Cascade),
+                None,
+            ),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"author_name\" TEXT NOT NULL, \"author_birthday\" DATE NOT NULL, CONSTRAINT \"posts_author_fkey\" FOREIGN KEY (\"author_name\", \"author_birthday\") REFERENCES \"users\"(\"name\", \"birthday\") ON DELETE CASCADE);")
+    );
+}
+
+#[test]
+fn foreign_constraint_set_null_columns() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("tenant_id", types::integer());
+        t.add_column("author_id", types::integer().nullable(true));
+        t.add_constraint(
+            "posts_author_fkey",
+            types::foreign_constraint(
+                &["tenant_id", "author_id"],
+                "users",
+                &["tenant_id", "id"],
+                Some(ReferentialAction::set_null_columns(&["author_id"])),
+                None,
+            ),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"tenant_id\" INTEGER NOT NULL, \"author_id\" INTEGER, CONSTRAINT \"posts_author_fkey\" FOREIGN KEY (\"tenant_id\", \"author_id\") REFERENCES \"users\"(\"tenant_id\", \"id\") ON DELETE SET NULL (\"author_id\"));")
+    );
+}
+
+#[test]
+fn check_constraint_no_inherit() {
+    let mut m = Migration::new();
+    m.create_table("events", |t: &mut Table| {
+        t.add_column("year", types::integer());
+        t.add_constraint("events_year_check", types::check_constraint("\"year\" > 2000"));
+        t.add_constraint(
+            "events_local_check",
+            types::check_constraint("\"year\" < 3000").no_inherit(true),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"events\" (\"year\" INTEGER NOT NULL, CONSTRAINT \"events_year_check\" CHECK (\"year\" > 2000), CONSTRAINT \"events_local_check\" CHECK (\"year\" < 3000) NO INHERIT);")
+    );
+}
+
+#[test]
+fn alter_table_foreign_constraint_actions() {
+    let mut m = Migration::new();
+    m.change_table("posts", |t: &mut Table| {
+        t.add_constraint(
+            "posts_author_fkey",
+            types::foreign_constraint(
+                &["author_id"],
+                "users",
+                &["id"],
+                Some(ReferentialAction::Cascade),
+                Some(ReferentialAction::Restrict),
+            ),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"posts\" ADD CONSTRAINT \"posts_author_fkey\" FOREIGN KEY (\"author_id\") REFERENCES \"users\"(\"id\") ON DELETE CASCADE ON UPDATE RESTRICT;")
+    );
+}
+
+#[test]
+fn json_array_check() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("tags", types::json());
+        t.add_constraint("posts_tags_check", types::json_array_check("tags"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"tags\" JSON NOT NULL, CONSTRAINT \"posts_tags_check\" CHECK (jsonb_typeof(\"tags\"::jsonb) = 'array'));")
+    );
+}
+
+#[test]
+fn foreign_constraint_column_mismatch() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("a", types::integer());
+        t.add_column("b", types::integer());
+        t.add_constraint(
+            "posts_fkey",
+            types::foreign_constraint(&["a", "b"], "t", &["x"], None, None),
+        );
+    });
+
+    let err = ValidationError::ForeignKeyColumnMismatch {
+        table: "posts".into(),
+        name: "posts_fkey".into(),
+        columns: 2,
+        refs: 1,
+    };
+    assert_eq!(m.validate::<Pg>(), Err(err.clone()));
+    assert_eq!(m.try_make::<Pg>(), Err(err));
+}
+
+#[test]
+fn foreign_column_mismatch() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("author", types::foreign("users", vec!["id", "name"]));
+    });
+
+    assert_eq!(
+        m.validate::<Pg>(),
+        Err(ValidationError::ForeignKeyColumnMismatch {
+            table: "posts".into(),
+            name: "author".into(),
+            columns: 1,
+            refs: 2,
+        })
+    );
+}
+
+#[test]
+fn not_null_column_without_default() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("nickname", types::text());
+    });
+
+    assert_eq!(
+        m.validate::<Pg>(),
+        Err(ValidationError::NotNullWithoutDefault {
+            table: "users".into(),
+            column: "nickname".into(),
+        })
+    );
+
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_column("nickname", types::text().default(""));
+        t.add_column("bio", types::text().nullable(true));
+    });
+    assert_eq!(m.validate::<Pg>(), Ok(()));
+
+    // New tables don't have any rows yet
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("nickname", types::text());
+    });
+    assert_eq!(m.validate::<Pg>(), Ok(()));
+}
+
+#[test]
+fn fk_style_inline() {
+    let mut m = Migration::new().fk_style(FkStyle::Inline);
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("author", types::foreign("users", "id"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"author\" INTEGER REFERENCES \"users\"(id) NOT NULL);")
+    );
+}
+
+#[test]
+fn fk_style_constraint() {
+    let mut m = Migration::new().fk_style(FkStyle::Constraint);
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("author", types::foreign("users", "id"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"author\" INTEGER NOT NULL, CONSTRAINT \"posts_author_fkey\" FOREIGN KEY (\"author\") REFERENCES \"users\"(\"id\"));")
+    );
+}
+
+#[test]
+fn defer_foreign_keys() {
+    let mut m = Migration::new().defer_foreign_keys(true);
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("id", types::primary());
+        t.add_column("avatar", types::nullable_foreign("images", "id"));
+    });
+    m.create_table("images", |t: &mut Table| {
+        t.add_column("id", types::primary());
+        t.add_column("owner", types::foreign("users", "id"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"avatar\" INTEGER);\
+             CREATE TABLE \"images\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"owner\" INTEGER NOT NULL);\
+             ALTER TABLE \"users\" ADD CONSTRAINT \"users_avatar_fkey\" FOREIGN KEY (\"avatar\") REFERENCES \"images\"(\"id\") ON DELETE SET NULL;\
+             ALTER TABLE \"images\" ADD CONSTRAINT \"images_owner_fkey\" FOREIGN KEY (\"owner\") REFERENCES \"users\"(\"id\");"
+        )
+    );
+}
+
+#[test]
+fn defer_foreign_keys_across_schemas() {
+    let mut m = Migration::new().defer_foreign_keys(true);
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("author", types::foreign_schema("accounts", "users", "id"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE TABLE \"posts\" (\"author\" INTEGER NOT NULL);\
+             ALTER TABLE \"posts\" ADD CONSTRAINT \"posts_author_fkey\" FOREIGN KEY (\"author\") REFERENCES \"accounts\".\"users\"(\"id\");"
+        )
+    );
+}
+
+#[test]
+fn unique_constraint() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("first_name", types::text());
+        t.add_column("last_name", types::text());
+        t.add_constraint(
+            "users_name_key",
+            types::unique_constraint(vec!["first_name", "last_name"]),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"first_name\" TEXT NOT NULL, \"last_name\" TEXT NOT NULL, CONSTRAINT \"users_name_key\" UNIQUE (\"first_name\", \"last_name\"));")
+    );
+}
+
+#[test]
+#[should_panic]
+fn unique_constraint_ordered() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", types::text());
+        t.add_constraint(
+            "users_name_key",
+            types::unique_constraint(vec![IndexColumn::new("name").desc()]),
+        );
+    });
+
+    m.make::<Pg>();
+}
+
+#[test]
+#[should_panic]
+fn unique_constraint_expression() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("email", types::text());
+        t.add_constraint(
+            "users_email_key",
+            types::unique_constraint(vec![IndexColumn::expression("lower(email)")]),
+        );
+    });
+
+    m.make::<Pg>();
+}
+
+#[test]
+fn constraint_comment() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("email", types::text());
+        t.add_constraint(
+            "users_email_key",
+            types::unique_constraint(vec!["email"]).comment("One account per user's address"),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"email\" TEXT NOT NULL, CONSTRAINT \"users_email_key\" UNIQUE (\"email\"));COMMENT ON CONSTRAINT \"users_email_key\" ON \"users\" IS 'One account per user''s address';")
+    );
+}
+
+#[test]
+fn no_overlap_constraint() {
+    let mut m = Migration::new();
+    m.create_table("reservations", |t: &mut Table| {
+        t.add_column("room", types::integer());
+        t.add_column("during", types::custom("TSRANGE"));
+        t.add_constraint("reservations_no_overlap", types::no_overlap_constraint("during"));
+        t.add_constraint(
+            "reservations_room_no_overlap",
+            types::exclude_constraint("gist", &[("room", "="), ("during", "&&")]),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"reservations\" (\"room\" INTEGER NOT NULL, \"during\" TSRANGE NOT NULL, CONSTRAINT \"reservations_no_overlap\" EXCLUDE USING gist (\"during\" WITH &&), CONSTRAINT \"reservations_room_no_overlap\" EXCLUDE USING gist (\"room\" WITH =, \"during\" WITH &&));")
+    );
+}
+
+#[test]
+fn identifier_too_long() {
+    let name = "a".repeat(64);
+
+    let mut m = Migration::new();
+    m.create_table(name.clone(), |t: &mut Table| {
+        t.add_column("id", types::primary());
+    });
+    assert_eq!(
+        m.validate::<Pg>(),
+        Err(ValidationError::IdentifierTooLong { name, max: 63 })
+    );
+
+    let mut m = Migration::new();
+    m.create_table("a".repeat(63), |t: &mut Table| {
+        t.add_column("id", types::primary());
+    });
+    assert_eq!(m.validate::<Pg>(), Ok(()));
+}
@@ -2,7 +2,7 @@
 #![allow(unused_imports)]
 
 use crate::backend::{Pg, SqlGenerator};
-use crate::{types, Migration, Table};
+use crate::{types, ColumnTemplate, KeywordCase, Migration, Quoting, RenderOptions, Summary, Table};
 
 #[test]
 fn simple_table() {
@@ -104,6 +104,37 @@ fn create_multiple_tables() {
     assert_eq!(m.make::<Pg>(), String::from("CREATE TABLE \"artist\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"name\" TEXT NOT NULL, \"description\" TEXT NOT NULL, \"pic\" TEXT NOT NULL, \"mbid\" TEXT NOT NULL);CREATE TABLE \"album\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"name\" TEXT NOT NULL, \"pic\" TEXT NOT NULL, \"mbid\" TEXT NOT NULL);"));
 }
 
+#[test]
+fn make_up_down() {
+    let mut up = Migration::new();
+    up.create_table("users", |t| {
+        t.add_column("name", types::text());
+    });
+    let mut down = Migration::new();
+    down.drop_table("users");
+
+    assert_eq!(
+        up.make_up_down::<Pg>(&down),
+        (
+            String::from("CREATE TABLE \"users\" (\"name\" TEXT NOT NULL);"),
+            String::from("DROP TABLE \"users\";")
+        )
+    );
+}
+
+#[test]
+fn add_generated_column() {
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.add_column("full_name", types::text().generated("\"first\" || ' ' || \"last\""));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"users\" ADD COLUMN \"full_name\" TEXT GENERATED ALWAYS AS (\"first\" || ' ' || \"last\") STORED NOT NULL;")
+    );
+}
+
 #[test]
 fn drop_table() {
     let mut m = Migration::new();
@@ -112,6 +143,63 @@ fn drop_table() {
     assert_eq!(m.make::<Pg>(), String::from("DROP TABLE \"users\";"));
 }
 
+#[test]
+fn create_view() {
+    let mut m = Migration::new();
+    m.create_view("active_users", "SELECT * FROM users WHERE active");
+    m.create_view("admins", "SELECT * FROM users WHERE admin")
+        .or_replace(true);
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE VIEW \"active_users\" AS SELECT * FROM users WHERE active;CREATE OR REPLACE VIEW \"admins\" AS SELECT * FROM users WHERE admin;")
+    );
+}
+
+#[test]
+fn create_view_with_check_option() {
+    let mut m = Migration::new();
+    m.create_view("active_users", "SELECT * FROM users WHERE active")
+        .check_option(true);
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE VIEW \"active_users\" AS SELECT * FROM users WHERE active WITH CHECK OPTION;")
+    );
+}
+
+#[test]
+fn drop_view() {
+    let mut m = Migration::new();
+    m.drop_view("active_users");
+
+    assert_eq!(m.make::<Pg>(), String::from("DROP VIEW \"active_users\";"));
+}
+
+#[test]
+fn create_materialized_view() {
+    let mut m = Migration::new();
+    m.create_materialized_view("user_counts", "SELECT count(*) FROM users");
+    m.drop_materialized_view("old_counts");
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE MATERIALIZED VIEW \"user_counts\" AS SELECT count(*) FROM users;DROP MATERIALIZED VIEW \"old_counts\";")
+    );
+}
+
+#[test]
+fn refresh_materialized_view() {
+    let mut m = Migration::new();
+    m.refresh_materialized_view("user_counts", false);
+    m.refresh_materialized_view("user_counts", true);
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("REFRESH MATERIALIZED VIEW \"user_counts\";REFRESH MATERIALIZED VIEW CONCURRENTLY \"user_counts\";")
+    );
+}
+
 #[test]
 fn drop_table_if_exists() {
     let mut m = Migration::new();
@@ -123,6 +211,14 @@ fn drop_table_if_exists() {
     );
 }
 
+#[test]
+fn drop_table_restrict() {
+    let mut m = Migration::new().schema("app");
+    m.drop_table_restrict("users");
+
+    assert_eq!(m.make::<Pg>(), String::from("DROP TABLE \"app\".\"users\" RESTRICT;"));
+}
+
 #[test]
 fn rename_table() {
     let mut m = Migration::new();
@@ -132,3 +228,622 @@ fn rename_table() {
         String::from("ALTER TABLE \"users\" RENAME TO \"cool_users\";")
     );
 }
+
+#[test]
+fn create_schema() {
+    let mut m = Migration::new();
+    m.create_schema("staging");
+    m.create_schema_if_not_exists("archive");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE SCHEMA \"staging\";CREATE SCHEMA IF NOT EXISTS \"archive\";")
+    );
+}
+
+#[test]
+fn move_table_to_schema() {
+    let mut m = Migration::new().schema("staging");
+    m.move_table_to_schema("users", "public");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"staging\".\"users\" SET SCHEMA \"public\";")
+    );
+}
+
+#[test]
+fn table_prefix() {
+    let mut m = Migration::new().table_prefix("app_");
+    m.create_table("posts", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("author", types::foreign("users", "id"));
+        t.add_index("posts_author_idx", types::index(vec!["author"]));
+    });
+    m.rename_table("tags", "labels");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"app_posts\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"author\" INTEGER REFERENCES \"app_users\"(id) NOT NULL);CREATE INDEX \"posts_author_idx\" ON \"app_posts\" (\"author\");ALTER TABLE \"app_tags\" RENAME TO \"app_labels\";")
+    );
+}
+
+#[test]
+fn set_table_owner() {
+    let mut m = Migration::new().schema("app");
+    m.set_table_owner("users", "app_admin");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"app\".\"users\" OWNER TO \"app_admin\";")
+    );
+}
+
+#[test]
+fn checksum() {
+    let migration = || {
+        let mut m = Migration::new();
+        m.create_table("users", |t| {
+            t.add_column("name", types::text());
+        });
+        m
+    };
+    assert_eq!(migration().checksum::<Pg>(), migration().checksum::<Pg>());
+    assert_eq!(migration().checksum::<Pg>().len(), 16);
+
+    let mut other = migration();
+    other.drop_table("posts");
+    assert_ne!(migration().checksum::<Pg>(), other.checksum::<Pg>());
+}
+
+#[test]
+fn summary() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("name", types::text());
+        t.add_index("users_name_idx", types::index(vec!["name"]));
+    });
+    m.create_view("admins", "SELECT * FROM users WHERE admin");
+    m.change_table("posts", |t| {
+        t.add_column("title", types::text().nullable(true));
+        t.drop_index("posts_slug_idx");
+        t.add_soft_delete(true);
+    });
+    m.rename_table("tags", "labels");
+    m.drop_table("sessions");
+    m.inject_custom("VACUUM");
+
+    assert_eq!(
+        m.summary(),
+        Summary {
+            creates: 2,
+            drops: 1,
+            alters: 2,
+            indexes: 3,
+        }
+    );
+}
+
+#[test]
+fn summary_index_groups() {
+    let mut m = Migration::new();
+    m.create_table("people", |t| {
+        t.add_column("first", types::text().index_group("people_name_idx"));
+        t.add_column("last", types::text().index_group("people_name_idx"));
+        t.add_column("email", types::text().index_group("people_email_idx"));
+        t.add_index("people_last_idx", types::index(vec!["last"]));
+    });
+
+    assert_eq!(m.summary().indexes, 3);
+}
+
+#[test]
+fn anonymous_unique() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("first", types::text());
+        t.add_column("last", types::text());
+        t.add_unique(&["first", "last"]);
+    });
+    m.change_table("posts", |t| t.add_unique(&["slug"]));
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"first\" TEXT NOT NULL, \"last\" TEXT NOT NULL, UNIQUE (\"first\", \"last\"));ALTER TABLE \"posts\" ADD UNIQUE (\"slug\");")
+    );
+}
+
+#[test]
+fn insert_rows() {
+    let mut m = Migration::new();
+    m.insert_rows(
+        "roles",
+        &["name", "level"],
+        vec![vec!["admin".into(), 10.into()], vec!["o'reilly".into(), 0.into()]],
+    );
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("INSERT INTO \"roles\" (\"name\", \"level\") VALUES ('admin', 10), ('o''reilly', 0);")
+    );
+}
+
+#[test]
+fn inject_custom_statements() {
+    let mut m = Migration::new();
+    m.inject_custom("SELECT 1; UPDATE \"users\" SET \"bio\" = 'a;b';");
+    m.inject_custom("SELECT 2");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("SELECT 1;UPDATE \"users\" SET \"bio\" = 'a;b';SELECT 2;")
+    );
+}
+
+#[test]
+fn inject_custom_raw() {
+    let mut m = Migration::new();
+    m.inject_custom_raw("CREATE FUNCTION one() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql;");
+    m.inject_custom("SELECT 2");
+    assert_eq!(
+        m.make_with::<Pg>(&RenderOptions::new().multiline(true).keyword_case(KeywordCase::Lower)),
+        String::from("CREATE FUNCTION one() RETURNS int AS $$ SELECT 1; $$ LANGUAGE sql;\nSELECT 2;")
+    );
+}
+
+#[test]
+fn cluster_table() {
+    let mut m = Migration::new().schema("app");
+    m.cluster_table("events", "events_created_at_idx");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CLUSTER \"app\".\"events\" USING \"events_created_at_idx\";")
+    );
+}
+
+#[test]
+fn set_table_logged() {
+    let mut m = Migration::new();
+    m.set_table_logged("imports", false);
+    m.set_table_logged("users", true);
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"imports\" SET UNLOGGED;ALTER TABLE \"users\" SET LOGGED;")
+    );
+}
+
+#[test]
+fn set_replica_identity() {
+    let mut m = Migration::new();
+    m.set_replica_identity("users", "FULL");
+    m.set_replica_identity("posts", "USING INDEX \"posts_pkey\"");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"users\" REPLICA IDENTITY FULL;ALTER TABLE \"posts\" REPLICA IDENTITY USING INDEX \"posts_pkey\";"
+        )
+    );
+}
+
+#[test]
+fn grant_and_revoke() {
+    let mut m = Migration::new();
+    m.grant(&["SELECT", "INSERT"], "users", "app");
+    m.revoke(&["DELETE"], "users", "app");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("GRANT SELECT, INSERT ON \"users\" TO \"app\";REVOKE DELETE ON \"users\" FROM \"app\";")
+    );
+}
+
+#[test]
+fn soft_delete() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("id", types::primary());
+        t.add_soft_delete(true);
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"deleted_at\" TIMESTAMP);CREATE INDEX \"posts_deleted_at_idx\" ON \"posts\" (\"deleted_at\") WHERE deleted_at IS NULL;")
+    );
+}
+
+#[test]
+fn polymorphic() {
+    let mut m = Migration::new();
+    m.create_table("comments", |t| {
+        t.add_column("id", types::primary());
+        t.add_polymorphic("commentable");
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"comments\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"commentable_type\" VARCHAR(255) NOT NULL, \"commentable_id\" INTEGER NOT NULL);CREATE INDEX \"comments_commentable_idx\" ON \"comments\" (\"commentable_type\", \"commentable_id\");")
+    );
+}
+
+#[test]
+fn timestamps() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("id", types::primary());
+        t.add_timestamps();
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"created_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL, \"updated_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL);")
+    );
+}
+
+#[test]
+fn audit_columns() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("id", types::primary());
+        t.add_audit_columns();
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"created_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL, \"updated_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL, \"created_by\" VARCHAR(255), \"updated_by\" VARCHAR(255));")
+    );
+}
+
+#[test]
+fn create_typed_table() {
+    let mut m = Migration::new();
+    m.create_typed_table("addresses", "address", |_| {});
+    m.create_typed_table("offices", "address", |t| {
+        t.add_unique(&["street", "city"]);
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"addresses\" OF \"address\";CREATE TABLE \"offices\" OF \"address\" (UNIQUE (\"street\", \"city\"));")
+    );
+}
+
+#[test]
+fn set_session() {
+    let mut m = Migration::new();
+    m.set_session("lock_timeout", "'5s'");
+    m.drop_table("users");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("SET lock_timeout = '5s';DROP TABLE \"users\";")
+    );
+}
+
+#[test]
+fn analyze_runs_last() {
+    let mut m = Migration::new();
+    m.analyze(Some("users"));
+    m.create_table("users", |t| {
+        t.add_column("id", types::primary());
+    });
+    m.analyze(None);
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY NOT NULL);ANALYZE \"users\";ANALYZE;")
+    );
+}
+
+#[test]
+fn create_table_as() {
+    let mut m = Migration::new().schema("archive");
+    m.create_table_as("old_users", "SELECT * FROM \"users\" WHERE \"deleted_at\" IS NOT NULL");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"archive\".\"old_users\" AS SELECT * FROM \"users\" WHERE \"deleted_at\" IS NOT NULL;")
+    );
+}
+
+#[test]
+fn storage_params() {
+    let mut m = Migration::new();
+    m.create_table("events", |t| {
+        t.add_column("id", types::primary());
+    })
+    .storage_param("OIDS", "FALSE")
+    .storage_param("fillfactor", "70");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"events\" (\"id\" SERIAL PRIMARY KEY NOT NULL) WITH (OIDS=FALSE, fillfactor=70);")
+    );
+}
+
+#[test]
+fn column_template() {
+    let id = ColumnTemplate::new("id", types::primary());
+    let created = ColumnTemplate::new("created_at", types::datetime().default(types::current_timestamp()));
+
+    let mut m = Migration::new();
+    for name in &["users", "posts"] {
+        let (id, created) = (id.clone(), created.clone());
+        m.create_table(*name, move |t| {
+            t.apply_template(&id);
+            t.apply_template(&created);
+        });
+    }
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"created_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL);CREATE TABLE \"posts\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"created_at\" TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL);")
+    );
+}
+
+#[test]
+fn create_sequence() {
+    let mut m = Migration::new().schema("app");
+    m.create_sequence("invoice_numbers", 1000, 1);
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE SEQUENCE \"app\".\"invoice_numbers\" START WITH 1000 INCREMENT BY 1;")
+    );
+}
+
+#[test]
+fn drop_sequence() {
+    let mut m = Migration::new();
+    m.drop_sequence("invoice_numbers");
+    assert_eq!(m.make::<Pg>(), String::from("DROP SEQUENCE \"invoice_numbers\";"));
+}
+
+#[test]
+fn set_sequence_owner() {
+    let mut m = Migration::new().schema("app");
+    m.set_sequence_owner("users_id_seq", "accounts", "id");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER SEQUENCE \"app\".\"users_id_seq\" OWNED BY \"app\".\"accounts\".\"id\";")
+    );
+}
+
+#[test]
+fn lower_case_keeps_user_sql() {
+    let mut m = Migration::new();
+    m.create_table("orders", |t| {
+        t.add_column("total", types::integer().generated("Price * Amount"));
+    });
+    m.create_view("big_orders", "SELECT * FROM Orders WHERE Total > 100");
+    m.inject_custom("UPDATE Orders SET Total = 0");
+
+    assert_eq!(
+        m.make_with::<Pg>(&RenderOptions::new().keyword_case(KeywordCase::Lower)),
+        String::from(
+            "create table \"orders\" (\"total\" integer generated always as (Price * Amount) stored not null);\
+             create view \"big_orders\" as SELECT * FROM Orders WHERE Total > 100;\
+             UPDATE Orders SET Total = 0;"
+        )
+    );
+}
+
+#[test]
+fn make_with_options() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("role", types::text().default("Admin"));
+        t.add_index("users_role_idx", types::index(vec!["role"]));
+    });
+    m.drop_table("Legacy");
+
+    let options = RenderOptions {
+        multiline: true,
+        comments: true,
+        keyword_case: KeywordCase::Lower,
+        semicolons: true,
+        quoting: Quoting::Always,
+        terminator: String::from(";"),
+    };
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from(
+            "-- Create table users\n\
+             create table \"users\" (\"id\" serial primary key not null, \"role\" text default 'Admin' not null);\n\
+             create index \"users_role_idx\" on \"users\" (\"role\");\n\
+             -- Drop table Legacy\n\
+             drop table \"Legacy\";"
+        )
+    );
+
+    let options = RenderOptions {
+        semicolons: false,
+        ..Default::default()
+    };
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from(
+            "CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"role\" TEXT DEFAULT 'Admin' NOT NULL)\n\
+             CREATE INDEX \"users_role_idx\" ON \"users\" (\"role\")\n\
+             DROP TABLE \"Legacy\""
+        )
+    );
+    assert_eq!(m.make_with::<Pg>(&RenderOptions::default()), m.make::<Pg>());
+}
+
+#[test]
+fn minimal_quoting() {
+    let mut m = Migration::new().schema("app");
+    m.create_table("user", |t| {
+        t.add_column("name", types::text().default("say \"hi\""));
+        t.add_column("Nickname", types::text());
+        t.add_column("order", types::integer());
+    });
+
+    let options = RenderOptions::new().quoting(Quoting::Minimal);
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from("CREATE TABLE app.\"user\" (name TEXT DEFAULT 'say \"hi\"' NOT NULL, \"Nickname\" TEXT NOT NULL, \"order\" INTEGER NOT NULL);")
+    );
+}
+
+#[test]
+fn minimal_quoting_embedded_quote() {
+    let mut m = Migration::new();
+    m.inject_custom("UPDATE \"we\"\"ird\" SET \"total\" = 0");
+
+    let options = RenderOptions::new().quoting(Quoting::Minimal);
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from("UPDATE \"we\"\"ird\" SET total = 0;")
+    );
+}
+
+#[test]
+fn render_options_builder() {
+    let options = RenderOptions::new();
+    assert_eq!(options, RenderOptions::default());
+    assert!(!options.multiline && !options.comments && options.semicolons);
+    assert_eq!(options.keyword_case, KeywordCase::Upper);
+
+    let mut m = Migration::new();
+    m.rename_table("users", "accounts");
+    m.drop_table("sessions");
+
+    let options = RenderOptions::new()
+        .multiline(true)
+        .comments(true)
+        .keyword_case(KeywordCase::Lower);
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from(
+            "-- Rename table users to accounts\n\
+             alter table \"users\" rename to \"accounts\";\n\
+             -- Drop table sessions\n\
+             drop table \"sessions\";"
+        )
+    );
+}
+
+#[test]
+fn render_terminator() {
+    let mut m = Migration::new();
+    m.rename_table("users", "accounts");
+    m.drop_table("sessions");
+
+    let options = RenderOptions::new().terminator(";\n");
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from(
+            "ALTER TABLE \"users\" RENAME TO \"accounts\";\n\
+             DROP TABLE \"sessions\";\n"
+        )
+    );
+
+    // A line break in the terminator already separates statements
+    let multiline = options.clone().multiline(true);
+    assert_eq!(m.make_with::<Pg>(&multiline), m.make_with::<Pg>(&options));
+
+    let options = RenderOptions::new().multiline(true).terminator(" $$");
+    assert_eq!(
+        m.make_with::<Pg>(&options),
+        String::from(
+            "ALTER TABLE \"users\" RENAME TO \"accounts\" $$\n\
+             DROP TABLE \"sessions\" $$"
+        )
+    );
+}
+
+#[test]
+fn attach_partition() {
+    let mut m = Migration::new();
+    m.attach_partition("measurements", "measurements_2020", "FROM ('2020-01-01') TO ('2021-01-01')");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"measurements\" ATTACH PARTITION \"measurements_2020\" FOR VALUES FROM ('2020-01-01') TO ('2021-01-01');")
+    );
+}
+
+#[test]
+fn detach_partition() {
+    let mut m = Migration::new().schema("metrics");
+    m.detach_partition("measurements", "measurements_2020");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"metrics\".\"measurements\" DETACH PARTITION \"metrics\".\"measurements_2020\";")
+    );
+}
+
+#[test]
+fn enum_default() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("status", types::enumeration("status", &["active", "inactive"]).default("active"));
+    });
+    m.create_table("groups", |t| {
+        t.add_column("status", types::enumeration("status", &["active", "inactive"]).nullable(true));
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TYPE \"status\" AS ENUM ('active', 'inactive');CREATE TABLE \"users\" (\"status\" \"status\" DEFAULT 'active'::\"status\" NOT NULL);CREATE TABLE \"groups\" (\"status\" \"status\");")
+    );
+}
+
+#[test]
+fn rename_enum_value() {
+    let mut m = Migration::new();
+    m.rename_enum_value("status", "inactive", "disabled");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TYPE \"status\" RENAME VALUE 'inactive' TO 'disabled';")
+    );
+}
+
+#[test]
+fn schema_enum() {
+    let mut m = Migration::new().schema("app");
+    m.create_table("users", |t| {
+        t.add_column("status", types::enumeration("status", &["active", "inactive"]).default("active"));
+    });
+    m.rename_enum_value("status", "inactive", "disabled");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "CREATE TYPE \"app\".\"status\" AS ENUM ('active', 'inactive');\
+             CREATE TABLE \"app\".\"users\" (\"status\" \"app\".\"status\" DEFAULT 'active'::\"app\".\"status\" NOT NULL);\
+             ALTER TYPE \"app\".\"status\" RENAME VALUE 'inactive' TO 'disabled';"
+        )
+    );
+}
+
+#[test]
+fn enum_array() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("roles", types::array(&types::enumeration("role", &["reader", "writer"])));
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TYPE \"role\" AS ENUM ('reader', 'writer');CREATE TABLE \"users\" (\"id\" SERIAL PRIMARY KEY NOT NULL, \"roles\" \"role\"[] NOT NULL);")
+    );
+}
+
+#[test]
+fn composite_primary_key() {
+    let mut m = Migration::new();
+    m.create_table("memberships", |t| {
+        t.add_column("user_id", types::integer().primary(true));
+        t.add_column("group_id", types::integer().primary(true));
+        t.add_column("role", types::text());
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"memberships\" (\"user_id\" INTEGER NOT NULL, \"group_id\" INTEGER NOT NULL, \"role\" TEXT NOT NULL, PRIMARY KEY (\"user_id\", \"group_id\"));")
+    );
+}
+
+#[test]
+fn create_table_like() {
+    let mut m = Migration::new();
+    m.create_table_like("users_copy", "users", &["DEFAULTS", "INDEXES"]);
+    m.create_table_like("users_bare", "users", &[]);
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users_copy\" (LIKE \"users\" INCLUDING DEFAULTS INCLUDING INDEXES);CREATE TABLE \"users_bare\" (LIKE \"users\");")
+    );
+}
+
+#[test]
+fn compression_and_storage() {
+    let mut m = Migration::new();
+    m.create_table("documents", |t| {
+        t.add_column("body", types::text().compression("lz4").storage("EXTERNAL"));
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"documents\" (\"body\" TEXT COMPRESSION lz4 NOT NULL);ALTER TABLE \"documents\" ALTER COLUMN \"body\" SET STORAGE EXTERNAL;")
+    );
+}
@@ -0,0 +1,206 @@
+//! Index creation and removal
+#![allow(unused_imports)]
+
+use crate::backend::{Pg, SqlGenerator};
+use crate::types::{self, IndexColumn};
+use crate::{Migration, Table, ValidationError};
+
+#[test]
+fn create_index() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", types::text());
+        t.add_index("users_name_idx", types::index(vec!["name"]));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"name\" TEXT NOT NULL);CREATE INDEX \"users_name_idx\" ON \"users\" (\"name\");")
+    );
+}
+
+#[test]
+fn create_index_in_schema() {
+    let mut m = Migration::new().schema("app");
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("name", types::text());
+        t.add_index("users_name_idx", types::index(vec!["name"]).unique(true));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"app\".\"users\" (\"name\" TEXT NOT NULL);CREATE UNIQUE INDEX \"users_name_idx\" ON \"app\".\"users\" (\"name\");")
+    );
+}
+
+#[test]
+fn create_index_on_materialized_view() {
+    let mut m = Migration::new().schema("app");
+    m.create_materialized_view("user_counts", "SELECT country, count(*) AS total FROM app.users GROUP BY country");
+    m.change_table("user_counts", |t: &mut Table| {
+        t.add_index("user_counts_country_idx", types::index(vec!["country"]).unique(true));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE MATERIALIZED VIEW \"app\".\"user_counts\" AS SELECT country, count(*) AS total FROM app.users GROUP BY country;CREATE UNIQUE INDEX \"user_counts_country_idx\" ON \"app\".\"user_counts\" (\"country\");")
+    );
+}
+
+#[test]
+fn index_comment() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index("users_name_idx", types::index(vec!["name"]).comment("Used by the user's search"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE INDEX \"users_name_idx\" ON \"users\" (\"name\");COMMENT ON INDEX \"users_name_idx\" IS 'Used by the user''s search';")
+    );
+}
+
+#[test]
+fn unique_expression_index() {
+    let mut m = Migration::new();
+    m.change_table("users", |t: &mut Table| {
+        t.add_index(
+            "users_email_key",
+            types::index(vec![IndexColumn::expression("lower(email)")]).unique(true),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE UNIQUE INDEX \"users_email_key\" ON \"users\" ((lower(email)));")
+    );
+}
+
+#[test]
+fn index_group() {
+    let mut m = Migration::new();
+    m.create_table("people", |t: &mut Table| {
+        t.add_column("first", types::text().index_group("people_name_idx"));
+        t.add_column("age", types::integer());
+        t.add_column("last", types::text().index_group("people_name_idx"));
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"people\" (\"first\" TEXT NOT NULL, \"age\" INTEGER NOT NULL, \"last\" TEXT NOT NULL);CREATE INDEX \"people_name_idx\" ON \"people\" (\"first\", \"last\");")
+    );
+}
+
+#[test]
+fn drop_index_in_schema() {
+    let mut m = Migration::new().schema("app");
+    m.change_table("users", |t: &mut Table| {
+        t.drop_index("users_name_idx");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("DROP INDEX \"app\".\"users_name_idx\";")
+    );
+}
+
+#[test]
+fn create_partial_index() {
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("email", types::text());
+        t.add_column("active", types::boolean());
+        t.add_partial_index("users_email_idx", types::index(vec!["email"]), "active");
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"users\" (\"email\" TEXT NOT NULL, \"active\" BOOLEAN NOT NULL);CREATE INDEX \"users_email_idx\" ON \"users\" (\"email\") WHERE active;")
+    );
+}
+
+#[test]
+fn create_index_ordered() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("title", types::text());
+        t.add_column("published_at", types::datetime().nullable(true));
+        t.add_index(
+            "posts_published_idx",
+            types::index(vec![
+                IndexColumn::new("published_at").desc().nulls_last(),
+                IndexColumn::new("title"),
+            ])
+            .unique(true),
+        );
+    });
+
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"title\" TEXT NOT NULL, \"published_at\" TIMESTAMP);CREATE UNIQUE INDEX \"posts_published_idx\" ON \"posts\" (\"published_at\" DESC NULLS LAST, \"title\");")
+    );
+}
+
+#[test]
+fn create_index_desc_nulls() {
+    let sql = Pg::create_index(
+        "posts",
+        None,
+        "posts_published_idx",
+        &types::index(vec![IndexColumn::new("published_at").desc()]),
+    );
+    assert_eq!(
+        sql,
+        String::from("CREATE INDEX \"posts_published_idx\" ON \"posts\" (\"published_at\" DESC)")
+    );
+
+    let sql = Pg::create_index(
+        "posts",
+        None,
+        "posts_published_idx",
+        &types::index(vec![IndexColumn::new("published_at").desc().nulls_last()]),
+    );
+    assert_eq!(
+        sql,
+        String::from("CREATE INDEX \"posts_published_idx\" ON \"posts\" (\"published_at\" DESC NULLS LAST)")
+    );
+}
+
+#[test]
+fn validate_partial_index_predicate() {
+    for predicate in &["", "  ", "(active AND (deleted_at IS NULL)", "active)"] {
+        let mut m = Migration::new();
+        m.create_table("users", move |t: &mut Table| {
+            t.add_column("email", types::varchar(255));
+            t.add_partial_index("users_email_idx", types::index(vec!["email"]), *predicate);
+        });
+
+        assert_eq!(
+            m.validate::<Pg>(),
+            Err(ValidationError::InvalidPredicate {
+                index: "users_email_idx".into(),
+                predicate: (*predicate).into(),
+            })
+        );
+    }
+
+    let mut m = Migration::new();
+    m.create_table("users", |t: &mut Table| {
+        t.add_column("email", types::varchar(255));
+        t.add_partial_index("users_email_idx", types::index(vec!["email"]), "(email <> ')')");
+    });
+    assert_eq!(m.validate::<Pg>(), Ok(()));
+}
+
+#[test]
+fn add_fulltext_gin() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t: &mut Table| {
+        t.add_column("search", types::tsvector());
+        t.add_fulltext_gin("search");
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE TABLE \"posts\" (\"search\" TSVECTOR NOT NULL);CREATE INDEX \"posts_search_idx\" ON \"posts\" USING gin (\"search\");")
+    );
+}
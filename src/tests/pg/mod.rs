@@ -1,6 +1,8 @@
 //! Test pgsql generation
 
 mod add_column;
+mod constraints;
 mod create_table;
+mod index;
 mod simple;
-mod reference;
\ No newline at end of file
+mod reference;
@@ -2,6 +2,7 @@
 #![allow(unused_imports)]
 
 use crate::backend::{Pg, SqlGenerator};
+use crate::types;
 
 #[test]
 fn create_table() {
@@ -60,6 +61,18 @@ fn drop_column() {
     assert_eq!(String::from("DROP COLUMN \"column_to_drop\""), sql);
 }
 
+#[test]
+fn drop_columns() {
+    let mut m = crate::Migration::new();
+    m.change_table("users", |t| {
+        t.drop_columns(&["nickname", "legacy_id"]);
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"users\" DROP COLUMN \"nickname\", DROP COLUMN \"legacy_id\";")
+    );
+}
+
 #[test]
 fn rename_column() {
     let sql = Pg::rename_column("old_column", "new_column");
@@ -68,3 +81,71 @@ fn rename_column() {
         sql
     );
 }
+
+#[test]
+fn drop_primary_key() {
+    let sql = Pg::drop_primary_key("users");
+    assert_eq!(String::from("DROP CONSTRAINT \"users_pkey\""), sql);
+}
+
+#[test]
+fn add_identity() {
+    let mut m = crate::Migration::new();
+    m.change_table("users", |t| {
+        t.add_identity("id", types::Identity::ByDefault);
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("ALTER TABLE \"users\" ALTER COLUMN \"id\" ADD GENERATED BY DEFAULT AS IDENTITY;")
+    );
+
+    let sql = Pg::add_identity("id", types::Identity::Always);
+    assert_eq!(String::from("ALTER COLUMN \"id\" ADD GENERATED ALWAYS AS IDENTITY"), sql);
+}
+
+#[test]
+fn drop_identity() {
+    let sql = Pg::drop_identity("id");
+    assert_eq!(String::from("ALTER COLUMN \"id\" DROP IDENTITY"), sql);
+}
+
+#[test]
+fn change_default() {
+    let mut m = crate::Migration::new();
+    m.change_table("users", |t| {
+        t.change_default("plan", "free");
+        t.change_default("credits", 10);
+    });
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from(
+            "ALTER TABLE \"users\" ALTER COLUMN \"plan\" SET DEFAULT 'free', ALTER COLUMN \"credits\" SET DEFAULT 10;"
+        )
+    );
+}
+
+#[test]
+fn set_collation() {
+    let sql = Pg::set_collation("name", "C", &types::text(), None);
+    assert_eq!(String::from("ALTER COLUMN \"name\" TYPE TEXT COLLATE \"C\""), sql);
+}
+
+#[test]
+fn drop_schema() {
+    assert_eq!(String::from("DROP SCHEMA \"staging\""), Pg::drop_schema("staging", false));
+    assert_eq!(
+        String::from("DROP SCHEMA \"staging\" CASCADE"),
+        Pg::drop_schema("staging", true)
+    );
+}
+
+#[test]
+fn comment_schema() {
+    let mut m = crate::Migration::new();
+    m.create_schema("billing");
+    m.comment_schema("billing", "Invoices and the customer's payments");
+    assert_eq!(
+        m.make::<Pg>(),
+        String::from("CREATE SCHEMA \"billing\";COMMENT ON SCHEMA \"billing\" IS 'Invoices and the customer''s payments';")
+    );
+}
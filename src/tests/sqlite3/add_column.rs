@@ -0,0 +1,115 @@
+//! All add_column combinations for sqlite3
+//!
+//! Sqlite derives a column affinity from the declared type name, the
+//! expected affinity of each mapping is noted above its test.
+#![allow(unused_imports)]
+
+use crate::backend::{SqlGenerator, Sqlite};
+use crate::types::{self, Affinity};
+
+// TEXT affinity
+#[test]
+fn text() {
+    let sql = Sqlite::add_column(true, None, "Text", &types::text());
+    assert_eq!(String::from("ADD COLUMN \"Text\" TEXT NOT NULL"), sql);
+}
+
+// TEXT affinity
+#[test]
+fn varchar() {
+    let sql = Sqlite::add_column(true, None, "Varchar", &types::varchar(255));
+    assert_eq!(
+        String::from("ADD COLUMN \"Varchar\" VARCHAR(255) NOT NULL"),
+        sql
+    );
+}
+
+// INTEGER affinity
+#[test]
+fn integer() {
+    let sql = Sqlite::add_column(true, None, "Integer", &types::integer());
+    assert_eq!(String::from("ADD COLUMN \"Integer\" INTEGER NOT NULL"), sql);
+}
+
+// NUMERIC affinity
+#[test]
+fn boolean() {
+    let sql = Sqlite::add_column(true, None, "Boolean", &types::boolean());
+    assert_eq!(String::from("ADD COLUMN \"Boolean\" BOOLEAN NOT NULL"), sql);
+}
+
+// NUMERIC affinity
+#[test]
+fn date() {
+    let sql = Sqlite::add_column(true, None, "Date", &types::date());
+    assert_eq!(String::from("ADD COLUMN \"Date\" DATE NOT NULL"), sql);
+}
+
+// BLOB affinity
+#[test]
+fn binary() {
+    let sql = Sqlite::add_column(true, None, "Binary", &types::binary());
+    assert_eq!(String::from("ADD COLUMN \"Binary\" BLOB NOT NULL"), sql);
+}
+
+#[test]
+fn with_affinity() {
+    let sql = Sqlite::add_column(true, None, "Amount", &types::with_affinity(Affinity::Numeric));
+    assert_eq!(String::from("ADD COLUMN \"Amount\" NUMERIC NOT NULL"), sql);
+
+    let sql = Sqlite::add_column(true, None, "Blob", &types::with_affinity(Affinity::Blob));
+    assert_eq!(String::from("ADD COLUMN \"Blob\" BLOB NOT NULL"), sql);
+}
+
+// INTEGER affinity, also allowed in STRICT tables
+#[test]
+fn integer_sized() {
+    for bytes in [1, 2, 4, 8].iter() {
+        let sql = Sqlite::add_column(true, None, "Count", &types::integer_sized(*bytes));
+        assert_eq!(String::from("ADD COLUMN \"Count\" INTEGER NOT NULL"), sql);
+    }
+}
+
+#[test]
+#[should_panic]
+fn integer_sized_invalid() {
+    types::integer_sized(3);
+}
+
+// NUMERIC affinity
+#[test]
+fn time_defaults() {
+    let sql = Sqlite::add_column(true, None, "At", &types::time().default(types::current_time()));
+    assert_eq!(String::from("ADD COLUMN \"At\" TIME DEFAULT CURRENT_TIME NOT NULL"), sql);
+
+    let sql = Sqlite::add_column(true, None, "On", &types::datetime().default(types::current_timestamp()));
+    assert_eq!(String::from("ADD COLUMN \"On\" DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL"), sql);
+}
+
+// NUMERIC affinity
+#[test]
+fn function_default() {
+    // Sqlite only accepts a function call as a default in parentheses
+    let sql = Sqlite::add_column(true, None, "On", &types::datetime().default(types::expression("datetime('now')")));
+    assert_eq!(String::from("ADD COLUMN \"On\" DATETIME DEFAULT (datetime('now')) NOT NULL"), sql);
+}
+
+#[test]
+fn float_and_double() {
+    let sql = Sqlite::add_column(true, None, "Ratio", &types::float());
+    assert_eq!(String::from("ADD COLUMN \"Ratio\" REAL NOT NULL"), sql);
+
+    let sql = Sqlite::add_column(true, None, "Ratio", &types::double());
+    assert_eq!(String::from("ADD COLUMN \"Ratio\" REAL NOT NULL"), sql);
+}
+
+// NUMERIC affinity
+#[test]
+fn decimal_default() {
+    let sql = Sqlite::add_column(true, None, "Price", &types::decimal(10, 2).default(10.5));
+    assert_eq!(String::from("ADD COLUMN \"Price\" DECIMAL(10, 2) DEFAULT 10.5 NOT NULL"), sql);
+
+    // Large values are written out, not in scientific notation
+    let sql = Sqlite::add_column(true, None, "Price", &types::decimal(30, 2).default(1e20));
+    assert_eq!(String::from("ADD COLUMN \"Price\" DECIMAL(30, 2) DEFAULT 100000000000000000000 NOT NULL"), sql);
+}
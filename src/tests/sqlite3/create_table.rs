@@ -2,7 +2,8 @@
 #![allow(unused_imports)]
 
 use crate::backend::{SqlGenerator, Sqlite};
-use crate::{types, Migration, Table};
+use crate::types::{self, IndexColumn};
+use crate::{Migration, Table};
 
 #[test]
 fn create_multiple_tables() {
@@ -35,3 +36,167 @@ fn create_table_if_not_exists_doesnt_hit_unreachable() {
     });
     assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE IF NOT EXISTS \"artist\" (\"id\" INTEGER NOT NULL PRIMARY KEY, \"name\" TEXT, \"description\" TEXT, \"pic\" TEXT, \"mbid\" TEXT);"));
 }
+
+#[test]
+fn create_index_in_schema() {
+    let mut m = Migration::new().schema("app");
+    m.create_table("users", |t| {
+        t.add_column("name", types::text());
+        t.add_index("users_name_idx", types::index(vec!["name"]));
+    });
+    assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE \"app\".\"users\" (\"name\" TEXT NOT NULL);CREATE INDEX \"app\".\"users_name_idx\" ON \"users\" (\"name\");"));
+}
+
+#[test]
+fn strict_table_booleans() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("id", types::primary());
+        t.add_column("active", types::boolean());
+        t.add_column("admin", types::boolean().nullable(true));
+    })
+    .strict(true);
+    assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE \"users\" (\"id\" INTEGER NOT NULL PRIMARY KEY, \"active\" INTEGER NOT NULL CHECK (\"active\" IN (0, 1)), \"admin\" INTEGER CHECK (\"admin\" IN (0, 1))) STRICT;"));
+}
+
+#[test]
+fn strict_table_types() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("name", types::varchar(255));
+        t.add_column("born", types::date().nullable(true));
+        t.add_column("seen", types::datetime().default(types::current_timestamp()));
+        t.add_column("balance", types::decimal(10, 2));
+        t.add_column("active", types::boolean().default(true));
+        t.add_column("admin", types::boolean().default(false));
+    })
+    .strict(true);
+    assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE \"users\" (\"name\" TEXT NOT NULL, \"born\" TEXT, \"seen\" TEXT DEFAULT CURRENT_TIMESTAMP NOT NULL, \"balance\" REAL NOT NULL, \"active\" INTEGER DEFAULT 1 NOT NULL CHECK (\"active\" IN (0, 1)), \"admin\" INTEGER DEFAULT 0 NOT NULL CHECK (\"admin\" IN (0, 1))) STRICT;"));
+}
+
+#[test]
+fn strict_boolean_quoted_name() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("is \"admin\"", types::boolean());
+    })
+    .strict(true);
+    assert!(m.make::<Sqlite>().contains("CHECK (\"is \"\"admin\"\"\" IN (0, 1))"));
+}
+
+#[test]
+fn timestamps() {
+    let mut m = Migration::new();
+    m.create_table("posts", |t| {
+        t.add_column("id", types::primary());
+        t.add_timestamps();
+    });
+    assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE \"posts\" (\"id\" INTEGER NOT NULL PRIMARY KEY, \"created_at\" DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL, \"updated_at\" DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL);"));
+}
+
+#[test]
+fn unique_constraint_ordered() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("name", types::text());
+        t.add_constraint(
+            "users_name_key",
+            types::unique_constraint(vec![IndexColumn::new("name").desc()]),
+        );
+    });
+    assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE \"users\" (\"name\" TEXT NOT NULL, CONSTRAINT \"users_name_key\" UNIQUE (\"name\" DESC));"));
+}
+
+#[test]
+fn anonymous_unique() {
+    let mut m = Migration::new();
+    m.create_table("users", |t| {
+        t.add_column("first", types::text());
+        t.add_column("last", types::text());
+        t.add_unique(&["first", "last"]);
+    });
+    assert_eq!(m.make::<Sqlite>(), String::from("CREATE TABLE \"users\" (\"first\" TEXT NOT NULL, \"last\" TEXT NOT NULL, UNIQUE (\"first\", \"last\"));"));
+}
+
+#[test]
+fn insert_rows() {
+    let mut m = Migration::new();
+    m.insert_rows(
+        "roles",
+        &["name", "level"],
+        vec![vec!["admin".into(), 10.into()], vec!["it's".into(), 0.into()]],
+    );
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("INSERT INTO \"roles\" (\"name\", \"level\") VALUES ('admin', 10), ('it''s', 0);")
+    );
+}
+
+#[test]
+fn insert_rows_booleans_and_dates() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let mut m = Migration::new();
+    m.insert_rows(
+        "users",
+        &["admin", "joined"],
+        vec![
+            vec![true.into(), (UNIX_EPOCH + Duration::from_secs(1_600_000_000)).into()],
+            vec![false.into(), UNIX_EPOCH.into()],
+        ],
+    );
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("INSERT INTO \"users\" (\"admin\", \"joined\") VALUES (1, '2020-09-13 12:26:40'), (0, '1970-01-01 00:00:00');")
+    );
+}
+
+#[test]
+fn rebuild_for_column_change() {
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.rebuild_for_column_change(vec![
+            ("id", types::primary()),
+            ("name", types::varchar(255).nullable(true)),
+        ]);
+        t.add_index("users_name_idx", types::index(vec!["name"]));
+    });
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"users_new\" (\"id\" INTEGER NOT NULL PRIMARY KEY, \"name\" VARCHAR(255));INSERT INTO \"users_new\" (\"id\", \"name\") SELECT \"id\", \"name\" FROM \"users\";DROP TABLE \"users\";ALTER TABLE \"users_new\" RENAME TO \"users\";CREATE INDEX \"users_name_idx\" ON \"users\" (\"name\");")
+    );
+}
+
+#[test]
+fn composite_primary_key() {
+    let mut m = Migration::new();
+    m.create_table("memberships", |t| {
+        t.add_column("user_id", types::integer().primary(true));
+        t.add_column("group_id", types::integer().primary(true));
+    });
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("CREATE TABLE \"memberships\" (\"user_id\" INTEGER NOT NULL, \"group_id\" INTEGER NOT NULL, PRIMARY KEY (\"user_id\", \"group_id\"));")
+    );
+}
+
+#[test]
+#[should_panic]
+fn create_or_replace_view() {
+    let mut m = Migration::new();
+    m.create_view("admins", "SELECT * FROM users WHERE admin")
+        .or_replace(true);
+    m.make::<Sqlite>();
+}
+
+#[test]
+fn drop_columns() {
+    let mut m = Migration::new();
+    m.change_table("users", |t| {
+        t.drop_columns(&["nickname", "legacy_id"]);
+    });
+    assert_eq!(
+        m.make::<Sqlite>(),
+        String::from("ALTER TABLE \"users\" DROP COLUMN \"nickname\";ALTER TABLE \"users\" DROP COLUMN \"legacy_id\";")
+    );
+}
@@ -1,6 +1,6 @@
 //! Builder API's module
 
-use super::impls::{BaseType, WrapVec};
+use super::impls::{Affinity, BaseType, ConstraintType, Identity, IndexColumn, ReferentialAction, WrapVec};
 use crate::types::Type;
 
 /// A standard primary numeric key type
@@ -35,6 +35,20 @@ pub fn integer() -> Type {
     Type::new(BaseType::Integer)
 }
 
+/// An integer identity column, generated from a sequence
+///
+/// `Identity::default()` lets inserts give their own value, which is
+/// usually what's wanted.
+///
+/// ```rust
+/// # use barrel::types::{self, Identity};
+/// let id = types::identity(Identity::default()).primary(true);
+/// let strict = types::identity(Identity::Always).primary(true);
+/// ```
+pub fn identity(mode: Identity) -> Type {
+    integer().identity(mode)
+}
+
 /// A 32-bit floating point type
 pub fn float() -> Type {
     Type::new(BaseType::Float)
@@ -79,6 +93,22 @@ where
     Type::new(BaseType::Foreign(None, table.into(), keys.into()))
 }
 
+/// A nullable foreign key that is set to `NULL` when its row is deleted
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::nullable_foreign("users", "id");
+/// ```
+pub fn nullable_foreign<S, I>(table: S, keys: I) -> Type
+where
+    S: Into<String>,
+    I: Into<WrapVec<String>>,
+{
+    foreign(table, keys)
+        .nullable(true)
+        .on_delete(ReferentialAction::SetNull)
+}
+
 /// Like `foreign(...)` but letting you provide an external schema
 ///
 /// This function is important when making cross-schema references
@@ -99,18 +129,272 @@ pub fn custom(sql: &'static str) -> Type {
     Type::new(BaseType::Custom(sql))
 }
 
+/// A column declared with a specific Sqlite type affinity
+///
+/// Sqlite derives a column's affinity from its declared type name,
+/// which means that the other builders only approximate it. This
+/// emits the affinity keyword directly and is only meaningful for
+/// `Sqlite`.
+pub fn with_affinity(affinity: Affinity) -> Type {
+    Type::new(BaseType::Custom(affinity.keyword()))
+}
+
 /// An SQL date type
 pub fn date() -> Type {
     Type::new(BaseType::Date)
 }
 
+/// An exact number with `precision` digits, `scale` of them after the point
+///
+/// Numeric defaults are rendered as-is, e.g. `DEFAULT 10.5`.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::decimal(10, 2).default(10.5);
+/// ```
+pub fn decimal(precision: usize, scale: usize) -> Type {
+    Type::new(BaseType::Decimal(precision, scale))
+}
+
+/// An amount of money, which is a `decimal(19, 4)`
+///
+/// This avoids the `MONEY` type of Postgres, whose format depends
+/// on the locale of the database.
+pub fn currency() -> Type {
+    decimal(19, 4)
+}
+
+/// An SQL timestamp type, without a time zone
+pub fn datetime() -> Type {
+    Type::new(BaseType::DateTime)
+}
+
+/// An SQL timestamp type, with a time zone
+///
+/// Postgres stores it as `TIMESTAMPTZ`. MySQL has no such type and
+/// uses `TIMESTAMP`, which is converted to UTC. Sqlite only keeps
+/// the text, so a time zone has to be part of the value.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::datetime_tz().default(types::current_timestamp());
+/// ```
+pub fn datetime_tz() -> Type {
+    Type::new(BaseType::DateTimeTz)
+}
+
+/// An integer that is stored in a specific number of bytes
+///
+/// The size has to be 1, 2, 4 or 8. Postgres has no 1-byte integer
+/// and uses `SMALLINT` instead, Sqlite stores all integers the same way.
+pub fn integer_sized(bytes: usize) -> Type {
+    match bytes {
+        1 | 2 | 4 | 8 => Type::new(BaseType::SizedInteger(bytes)),
+        _ => panic!("Integers can only be 1, 2, 4 or 8 bytes wide!"),
+    }
+}
+
+/// A column that only allows one of a set of values
+///
+/// Postgres creates a named `ENUM` type before the table that first
+/// uses it and casts defaults to that type. MySQL declares the
+/// values inline, Sqlite uses `TEXT` with a `CHECK` constraint.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::enumeration("status", &["active", "inactive"]).default("active");
+/// ```
+pub fn enumeration<S: Into<String>>(name: S, variants: &[&str]) -> Type {
+    Type::new(BaseType::Enum(
+        name.into(),
+        variants.iter().map(|v| (*v).into()).collect(),
+    ))
+}
+
+/// A spatial column that holds any kind of geometry
+///
+/// Postgres needs the PostGIS extension for it. Not supported by
+/// Sqlite.
+pub fn geometry() -> Type {
+    Type::new(BaseType::Geometry("GEOMETRY".into()))
+}
+
+/// A spatial column that holds a single point
+pub fn point() -> Type {
+    Type::new(BaseType::Geometry("POINT".into()))
+}
+
+/// A Postgres write-ahead log sequence number
+pub fn pg_lsn() -> Type {
+    Type::new(BaseType::PgLsn)
+}
+
+/// A Postgres text search query
+pub fn tsquery() -> Type {
+    Type::new(BaseType::TsQuery)
+}
+
+/// A Postgres text search document
+pub fn tsvector() -> Type {
+    Type::new(BaseType::TsVector)
+}
+
+/// A Postgres text search document generated from text columns
+///
+/// `config` is the text search configuration used to parse the
+/// columns, e.g. `english` or `simple`. Multiple columns are joined
+/// with a space, ignoring `NULL` values.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::tsvector_generated("simple", &["title", "body"]);
+/// ```
+pub fn tsvector_generated(config: &str, columns: &[&str]) -> Type {
+    let document = match columns {
+        [column] => format!("\"{}\"", column),
+        _ => columns
+            .iter()
+            .map(|c| format!("coalesce(\"{}\", '')", c))
+            .collect::<Vec<_>>()
+            .join(" || ' ' || "),
+    };
+
+    tsvector().generated(format!("to_tsvector('{}', {})", config, document))
+}
+
+/// An SQL time of day type
+pub fn time() -> Type {
+    Type::new(BaseType::Time)
+}
+
 /// Create an array of inner types
 pub fn array(inner: &Type) -> Type {
     Type::new(BaseType::Array(Box::new(inner.get_inner())))
 }
 
 /// Create an index over multiple, existing columns of the same type
-pub fn index<S: Into<String>>(columns: Vec<S>) -> Type {
-    let vec: Vec<String> = columns.into_iter().map(|s| s.into()).collect();
+pub fn index<S: Into<IndexColumn>>(columns: Vec<S>) -> Type {
+    let vec: Vec<IndexColumn> = columns.into_iter().map(|s| s.into()).collect();
     Type::new(BaseType::Index(vec))
 }
+
+/// Create a unique constraint over one or more columns
+///
+/// Sqlite and MySQL accept a sort order for the columns of the
+/// constraint (`UNIQUE ("a" DESC)`). Postgres doesn't, so there the
+/// columns need to be unordered; use a unique `index` instead.
+/// Expressions are rejected by every backend, they need an index too.
+pub fn unique_constraint<S: Into<IndexColumn>>(columns: Vec<S>) -> Type {
+    let vec: Vec<IndexColumn> = columns.into_iter().map(|s| s.into()).collect();
+    let names = vec.iter().map(|c| c.name.clone()).collect();
+    Type::new(BaseType::Constraint(ConstraintType::Unique(vec), names))
+}
+
+/// Create an exclusion constraint, only supported by Postgres
+///
+/// Each element pairs a column with the operator used to compare it.
+/// No two rows may have values for which all operators return true.
+///
+/// ```rust
+/// # use barrel::types;
+/// let c = types::exclude_constraint("gist", &[("room", "="), ("during", "&&")]);
+/// ```
+pub fn exclude_constraint(method: &str, elements: &[(&str, &str)]) -> Type {
+    Type::new(BaseType::Constraint(
+        ConstraintType::Exclude {
+            method: method.into(),
+            operators: elements.iter().map(|(_, op)| (*op).into()).collect(),
+        },
+        elements.iter().map(|(col, _)| (*col).into()).collect(),
+    ))
+}
+
+/// Forbid overlapping ranges in a column, only supported by Postgres
+///
+/// This is the common case of `exclude_constraint` and renders
+/// `EXCLUDE USING gist ("range_column" WITH &&)`.
+pub fn no_overlap_constraint(range_column: &str) -> Type {
+    exclude_constraint("gist", &[(range_column, "&&")])
+}
+
+/// Create a check constraint from a raw SQL expression
+///
+/// ```rust
+/// # use barrel::{types, Migration};
+/// # let mut m = Migration::new();
+/// m.create_table("events", |t| {
+///     t.add_column("year", types::integer());
+///     t.add_constraint("events_year_check", types::check_constraint("year > 2000").no_inherit(true));
+/// });
+/// ```
+pub fn check_constraint(expr: &str) -> Type {
+    Type::new(BaseType::Constraint(
+        ConstraintType::Check {
+            expr: expr.into(),
+            no_inherit: false,
+        },
+        vec![],
+    ))
+}
+
+/// Check that the JSON in a column is an array
+///
+/// Postgres renders `jsonb_typeof(..)`, which works for `JSON` and
+/// `JSONB` columns, MySQL `JSON_TYPE(..)` and Sqlite `json_type(..)`.
+///
+/// ```rust
+/// # use barrel::{types, Migration};
+/// # let mut m = Migration::new();
+/// m.create_table("posts", |t| {
+///     t.add_column("tags", types::json());
+///     t.add_constraint("posts_tags_check", types::json_array_check("tags"));
+/// });
+/// ```
+pub fn json_array_check(column: &str) -> Type {
+    Type::new(BaseType::Constraint(ConstraintType::JsonArray, vec![column.into()]))
+}
+
+/// Create a foreign key constraint over one or more columns
+///
+/// Each of `columns` references the column at the same position in
+/// `refs`, so both need to be of the same length. Use
+/// `Migration::validate` to check this before generating SQL.
+///
+/// ```rust
+/// # use barrel::{types, Migration};
+/// # use barrel::types::ReferentialAction;
+/// # let mut m = Migration::new();
+/// m.create_table("posts", |t| {
+///     t.add_column("author_name", types::text());
+///     t.add_column("author_birthday", types::date());
+///     t.add_constraint(
+///         "posts_author_fkey",
+///         types::foreign_constraint(
+///             &["author_name", "author_birthday"],
+///             "users",
+///             &["name", "birthday"],
+///             Some(ReferentialAction::Cascade),
+///             None,
+///         ),
+///     );
+/// });
+/// ```
+pub fn foreign_constraint(
+    columns: &[&str],
+    table: &str,
+    refs: &[&str],
+    on_delete: Option<ReferentialAction>,
+    on_update: Option<ReferentialAction>,
+) -> Type {
+    Type::new(BaseType::Constraint(
+        ConstraintType::Foreign {
+            schema: None,
+            table: table.into(),
+            refs: refs.iter().map(|s| s.to_string()).collect(),
+            on_delete,
+            on_update,
+            index: None,
+        },
+        columns.iter().map(|s| s.to_string()).collect(),
+    ))
+}
@@ -1,5 +1,5 @@
 use std::fmt::{self, Display, Formatter};
-use std::time::SystemTime;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::Type;
 
@@ -27,6 +27,47 @@ pub enum WrappedDefault<'outer> {
     Custom(&'static str),
     /// Any of the above, but **many** of them
     Array(Vec<Type>),
+    /// The elements of an array column, rendered as `'{a,b}'`
+    ArrayLiteral(Vec<String>),
+    /// The time at which a row is written, evaluated by the database
+    CurrentTimestamp,
+    /// The time of day at which a row is written, evaluated by the database
+    CurrentTime,
+    /// A random UUID, generated by the database
+    RandomUuid,
+    /// An SQL expression, evaluated by the database
+    Expression(String),
+    /// Another default, cast to a type (only rendered by Postgres)
+    Cast(Box<WrappedDefault<'outer>>, String),
+}
+
+impl<'outer> WrappedDefault<'outer> {
+    /// Cast this default to a specific type
+    ///
+    /// Postgres renders the cast as `<default>::<type>`, other
+    /// backends ignore it and only render the value.
+    ///
+    /// ```rust
+    /// # use barrel::types::{self, WrappedDefault};
+    /// let col = types::json().default(WrappedDefault::AnyText("{}").cast("jsonb"));
+    /// ```
+    pub fn cast<S: Into<String>>(self, to: S) -> Self {
+        WrappedDefault::Cast(Box::new(self), to.into())
+    }
+
+    /// The SQL written by the user that this default is rendered with
+    pub(crate) fn user_sql(&self) -> Vec<String> {
+        match self {
+            WrappedDefault::Custom(sql) => vec![sql.to_string()],
+            WrappedDefault::Expression(sql) => vec![sql.clone()],
+            WrappedDefault::Cast(d, to) => {
+                let mut sql = d.user_sql();
+                sql.push(to.clone());
+                sql
+            }
+            _ => vec![],
+        }
+    }
 }
 
 impl<'outer> Display for WrappedDefault<'outer> {
@@ -42,16 +83,81 @@ impl<'outer> Display for WrappedDefault<'outer> {
                 Double(ref val) => format!("{}", val),
                 UUID(ref val) => format!("{}", val),
                 Boolean(ref val) => format!("{}", val),
-                Date(ref val) => format!("{:?}", val),
+                Date(ref val) => format_timestamp(val),
                 Binary(ref val) => format!("{:?}", val),
                 Foreign(ref val) => format!("{:?}", val),
                 Custom(ref val) => format!("{}", val),
                 Array(ref val) => format!("{:?}", val),
+                ArrayLiteral(ref val) => format!("{{{}}}", val.join(",")),
+                CurrentTimestamp => String::from("CURRENT_TIMESTAMP"),
+                CurrentTime => String::from("CURRENT_TIME"),
+                RandomUuid => String::from("UUID()"),
+                Expression(ref val) => val.clone(),
+                Cast(ref val, _) => format!("{}", val),
             }
         )
     }
 }
 
+/// Format a point in time as an ISO-8601 timestamp in UTC
+///
+/// The date and time are separated by a space, which every backend
+/// accepts. Microseconds are only added if there are any.
+fn format_timestamp(time: &SystemTime) -> String {
+    let (secs, micros) = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() as i64, d.subsec_micros()),
+        Err(e) => match e.duration() {
+            d if d.subsec_micros() == 0 => (-(d.as_secs() as i64), 0),
+            d => (-(d.as_secs() as i64) - 1, 1_000_000 - d.subsec_micros()),
+        },
+    };
+
+    // Days to a civil date, from http://howardhinnant.github.io/date_algorithms.html
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    let z = days + 719_468;
+    let (era, doe) = (z.div_euclid(146_097), z.rem_euclid(146_097));
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    let stamp = format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    );
+    match micros {
+        0 => stamp,
+        _ => format!("{}.{:06}", stamp, micros),
+    }
+}
+
+/// Default to the time at which a row is written
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::datetime().default(types::current_timestamp());
+/// ```
+pub fn current_timestamp() -> WrappedDefault<'static> {
+    WrappedDefault::CurrentTimestamp
+}
+
+/// Default to the time of day at which a row is written
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::time().default(types::current_time());
+/// ```
+pub fn current_time() -> WrappedDefault<'static> {
+    WrappedDefault::CurrentTime
+}
+
 impl From<&'static str> for WrappedDefault<'static> {
     fn from(s: &'static str) -> Self {
         WrappedDefault::AnyText(s)
@@ -87,3 +193,55 @@ impl From<SystemTime> for WrappedDefault<'static> {
         WrappedDefault::Date(s)
     }
 }
+
+/// Default an array column to some elements
+///
+/// The elements are written as they are displayed, so this is meant
+/// for numbers and simple words, not text with commas or quotes.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::array(&types::integer()).default(types::array_default(vec![1, 2, 3]));
+/// ```
+pub fn array_default<T: Display>(values: Vec<T>) -> WrappedDefault<'static> {
+    WrappedDefault::ArrayLiteral(values.iter().map(|v| v.to_string()).collect())
+}
+
+/// Default to a random UUID
+///
+/// Postgres (13+) calls `gen_random_uuid()`, MySQL (8+) stores
+/// `UUID()` in its `CHAR(36)` fallback column. Not supported by Sqlite.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::uuid().default(types::random_uuid());
+/// ```
+pub fn random_uuid() -> WrappedDefault<'static> {
+    WrappedDefault::RandomUuid
+}
+
+/// Default to an SQL expression, which is rendered as-is
+///
+/// MySQL and Sqlite need expressions in parentheses, which are
+/// added for them.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::integer().default(types::expression("1 + 1"));
+/// ```
+pub fn expression<S: Into<String>>(sql: S) -> WrappedDefault<'static> {
+    WrappedDefault::Expression(sql.into())
+}
+
+/// Default to the next value of a sequence, only supported by Postgres
+///
+/// This is what a `SERIAL` column does with its own sequence. A
+/// `SERIAL` can't have another default, so use an integer column.
+///
+/// ```rust
+/// # use barrel::types;
+/// let col = types::integer().default(types::nextval("invoice_numbers"));
+/// ```
+pub fn nextval(sequence: &str) -> WrappedDefault<'static> {
+    WrappedDefault::Expression(format!("nextval('{}')", sequence.replace('\'', "''")))
+}
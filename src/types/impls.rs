@@ -17,6 +17,10 @@ pub enum BaseType {
     Primary,
     /// Simple integer
     Integer,
+    /// Integer with a storage size in bytes (1, 2, 4 or 8)
+    SizedInteger(usize),
+    /// Exact number with a `precision` and `scale`
+    Decimal(usize, usize),
     /// Floating point number
     Float,
     /// Like Float but `~ ~ d o u b l e    p r e c i s i o n ~ ~`
@@ -29,16 +33,273 @@ pub enum BaseType {
     Json,
     /// Date And Time
     Date,
+    /// A point in time, without a time zone
+    DateTime,
+    /// A point in time, with a time zone
+    DateTimeTz,
+    /// A time of day, without a date
+    Time,
     /// <inconceivable jibberish>
     Binary,
+    /// A Postgres write-ahead log location
+    PgLsn,
+    /// A Postgres full text search query
+    TsQuery,
+    /// A Postgres full text search document
+    TsVector,
+    /// A spatial value of a geometry kind, e.g. `POINT`
+    Geometry(String),
     /// Foreign key to other table
     Foreign(Option<String>, String, WrapVec<String>),
+    /// One of a fixed set of text values (`name`, `variants`)
+    Enum(String, Vec<String>),
     /// I have no idea what you are – but I *like* it
     Custom(&'static str),
     /// Any of the above, but **many** of them
     Array(Box<BaseType>),
     /// Indexing over multiple columns
-    Index(Vec<String>),
+    Index(Vec<IndexColumn>),
+    /// A table-level constraint over multiple columns
+    Constraint(ConstraintType, Vec<String>),
+}
+
+/// Where `NULL` values are sorted in an index
+///
+/// Without it, the database decides: Postgres sorts `NULL` values
+/// as if they were larger than any other value, so they come last in
+/// ascending and first in descending order.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Nulls {
+    /// Before all other values
+    First,
+    /// After all other values
+    Last,
+}
+
+/// A column in an index, with an optional sort order
+///
+/// Plain column names convert into an ascending `IndexColumn`, so
+/// they can be used wherever an index column is expected.
+///
+/// ```rust
+/// # use barrel::types::{self, IndexColumn};
+/// let idx = types::index(vec![IndexColumn::new("created_at").desc().nulls_last()]);
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+pub struct IndexColumn {
+    pub name: String,
+    pub expression: bool,
+    pub descending: bool,
+    pub nulls: Option<Nulls>,
+}
+
+impl IndexColumn {
+    /// Index a column in ascending order
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            expression: false,
+            descending: false,
+            nulls: None,
+        }
+    }
+
+    /// Index the result of an SQL expression instead of a column
+    ///
+    /// The expression is raw SQL, e.g. `lower(email)`, and can only be
+    /// used in indices, not in constraints. MySQL needs 8.0.13 or newer.
+    ///
+    /// ```rust
+    /// # use barrel::types::{self, IndexColumn};
+    /// let idx = types::index(vec![IndexColumn::expression("lower(email)")]).unique(true);
+    /// ```
+    pub fn expression<S: Into<String>>(sql: S) -> Self {
+        Self {
+            expression: true,
+            ..Self::new(sql)
+        }
+    }
+
+    /// The column name in quotes, or the expression in parentheses
+    pub(crate) fn print(&self, quote: char) -> String {
+        match self.expression {
+            true => format!("({})", self.name),
+            false => format!("{}{}{}", quote, self.name, quote),
+        }
+    }
+
+    /// Sort this column in descending order
+    pub fn desc(self) -> Self {
+        Self { descending: true, ..self }
+    }
+
+    /// Sort `NULL` values first (only rendered by Postgres)
+    ///
+    /// `NULLS` is only rendered when set, so a descending column
+    /// keeps the default of the database otherwise.
+    pub fn nulls_first(self) -> Self {
+        Self { nulls: Some(Nulls::First), ..self }
+    }
+
+    /// Sort `NULL` values last (only rendered by Postgres)
+    pub fn nulls_last(self) -> Self {
+        Self { nulls: Some(Nulls::Last), ..self }
+    }
+
+    /// Whether any sort order was given for this column
+    pub fn is_ordered(&self) -> bool {
+        self.descending || self.nulls.is_some()
+    }
+}
+
+impl<'a> From<&'a str> for IndexColumn {
+    fn from(s: &'a str) -> Self {
+        IndexColumn::new(s)
+    }
+}
+
+impl From<String> for IndexColumn {
+    fn from(s: String) -> Self {
+        IndexColumn::new(s)
+    }
+}
+
+/// The kind of a table-level constraint
+#[derive(PartialEq, Debug, Clone)]
+pub enum ConstraintType {
+    /// The columns are unique, optionally with a sort order for
+    /// the index backing the constraint
+    Unique(Vec<IndexColumn>),
+    /// The columns reference the `refs` columns of another table
+    Foreign {
+        /// The schema of the referenced table, if not the migration's
+        schema: Option<String>,
+        table: String,
+        refs: Vec<String>,
+        on_delete: Option<ReferentialAction>,
+        on_update: Option<ReferentialAction>,
+        /// The name of the index backing the key (only used by MySQL)
+        index: Option<String>,
+    },
+    /// No two rows may match on all columns, each compared with its
+    /// operator, using an index of the given `method`
+    Exclude { method: String, operators: Vec<String> },
+    /// Every row must satisfy an SQL expression, which can be kept
+    /// from being inherited by child tables (only Postgres)
+    Check { expr: String, no_inherit: bool },
+    /// The JSON value of the column is an array
+    JsonArray,
+}
+
+/// When the database generates the value of an identity column
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Identity {
+    /// Always, inserting or updating a value is an error
+    Always,
+    /// Only when no value is given (the default)
+    ByDefault,
+}
+
+// Deriving it needs `#[default]`, which is too new for our MSRV
+#[allow(clippy::derivable_impls)]
+impl Default for Identity {
+    fn default() -> Self {
+        Identity::ByDefault
+    }
+}
+
+impl Identity {
+    /// The SQL between `GENERATED` and `AS IDENTITY`
+    pub(crate) fn keyword(&self) -> &'static str {
+        match self {
+            Identity::Always => "ALWAYS",
+            Identity::ByDefault => "BY DEFAULT",
+        }
+    }
+}
+
+/// Whether the length of a string column counts characters or bytes
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum LengthSemantics {
+    /// The length limits the number of characters
+    Char,
+    /// The length limits the number of bytes
+    Byte,
+}
+
+/// What happens to a row when the row it references changes
+#[derive(PartialEq, Debug, Clone)]
+pub enum ReferentialAction {
+    /// Fail if referencing rows still exist, but only at the end of a statement
+    NoAction,
+    /// Fail immediately if referencing rows still exist
+    Restrict,
+    /// Delete or update the referencing rows too
+    Cascade,
+    /// Set the referencing columns to `NULL`
+    SetNull,
+    /// Only set some of the referencing columns to `NULL`
+    ///
+    /// Only Postgres (15+) can limit this to specific columns, and only
+    /// for `ON DELETE`. Other backends set all referencing columns.
+    SetNullColumns(Vec<String>),
+    /// Set the referencing columns to their default value
+    SetDefault,
+}
+
+impl ReferentialAction {
+    /// Only set some of the referencing columns to `NULL`
+    ///
+    /// ```rust
+    /// # use barrel::types::ReferentialAction;
+    /// let action = ReferentialAction::set_null_columns(&["author_id"]);
+    /// ```
+    pub fn set_null_columns(columns: &[&str]) -> Self {
+        ReferentialAction::SetNullColumns(columns.iter().map(|c| c.to_string()).collect())
+    }
+
+    /// The SQL keyword for this action
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            ReferentialAction::NoAction => "NO ACTION",
+            ReferentialAction::Restrict => "RESTRICT",
+            ReferentialAction::Cascade => "CASCADE",
+            ReferentialAction::SetNull | ReferentialAction::SetNullColumns(_) => "SET NULL",
+            ReferentialAction::SetDefault => "SET DEFAULT",
+        }
+    }
+}
+
+/// The type affinity of a Sqlite column
+///
+/// Sqlite doesn't enforce column types but instead derives one of
+/// five affinities from the declared type name. Use this with
+/// `types::with_affinity` to force a specific affinity keyword.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Affinity {
+    /// Stored as `TEXT`
+    Text,
+    /// Stored as `NUMERIC`
+    Numeric,
+    /// Stored as `INTEGER`
+    Integer,
+    /// Stored as `REAL`
+    Real,
+    /// Stored as `BLOB`, exactly as it was input
+    Blob,
+}
+
+impl Affinity {
+    /// The keyword that makes Sqlite pick this affinity
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Affinity::Text => "TEXT",
+            Affinity::Numeric => "NUMERIC",
+            Affinity::Integer => "INTEGER",
+            Affinity::Real => "REAL",
+            Affinity::Blob => "BLOB",
+        }
+    }
 }
 
 /// A database column type and all the metadata attached to it
@@ -82,6 +343,20 @@ pub struct Type {
     pub indexed: bool,
     pub primary: bool,
     pub default: Option<WrappedDefault<'static>>,
+    pub on_update: Option<WrappedDefault<'static>>,
+    pub not_null_name: Option<String>,
+    pub length_semantics: Option<LengthSemantics>,
+    pub comment: Option<String>,
+    pub compression: Option<String>,
+    pub storage: Option<String>,
+    pub generated: Option<String>,
+    pub index_group: Option<String>,
+    pub index_method: Option<String>,
+    pub invisible: bool,
+    pub collation: Option<String>,
+    pub identity: Option<Identity>,
+    pub srid: Option<u32>,
+    pub on_delete: Option<ReferentialAction>,
     pub size: Option<usize>,
     pub inner: BaseType,
 }
@@ -97,6 +372,20 @@ impl Type {
             indexed: false,
             primary: false,
             default: None,
+            on_update: None,
+            not_null_name: None,
+            length_semantics: None,
+            comment: None,
+            compression: None,
+            storage: None,
+            generated: None,
+            index_group: None,
+            index_method: None,
+            invisible: false,
+            collation: None,
+            identity: None,
+            srid: None,
+            on_delete: None,
             size: None,
             inner,
         }
@@ -107,11 +396,41 @@ impl Type {
         self.inner.clone()
     }
 
+    /// The SQL written by the user that this type is rendered with
+    ///
+    /// That's expressions, custom types and constraint conditions.
+    pub(crate) fn user_sql(&self) -> Vec<String> {
+        let mut sql: Vec<String> = self.default.iter().chain(&self.on_update).flat_map(|d| d.user_sql()).collect();
+        sql.extend(self.generated.clone());
+
+        let mut inner = &self.inner;
+        while let BaseType::Array(ref t) = inner {
+            inner = t;
+        }
+        match inner {
+            BaseType::Custom(t) => sql.push(t.to_string()),
+            BaseType::Index(cols) => sql.extend(cols.iter().filter(|c| c.expression).map(|c| c.name.clone())),
+            BaseType::Constraint(ConstraintType::Check { expr, .. }, _) => sql.push(expr.clone()),
+            BaseType::Constraint(ConstraintType::Exclude { operators, .. }, _) => sql.extend(operators.clone()),
+            _ => {}
+        }
+
+        sql
+    }
+
     /// Set the nullability of this type
     pub fn nullable(self, arg: bool) -> Self {
         Self { nullable: arg, ..self }
     }
 
+    /// Make this type non-nullable through a named `NOT NULL` constraint
+    ///
+    /// Postgres and Sqlite render `CONSTRAINT <name> NOT NULL`, MySQL
+    /// can't name the constraint and falls back to `NOT NULL`.
+    pub fn not_null_named<S: Into<String>>(self, name: S) -> Self {
+        Self { nullable: false, not_null_name: Some(name.into()), ..self }
+    }
+
     /// Set the uniqueness of this type
     pub fn unique(self, arg: bool) -> Self {
         Self { unique: arg, ..self }
@@ -137,6 +456,170 @@ impl Type {
         Self { default: Some(arg.into()), ..self }
     }
 
+    /// Provide a value that is written whenever a row is updated
+    ///
+    /// This is only supported by MySQL and ignored by other backends.
+    pub fn on_update(self, arg: impl Into<WrappedDefault<'static>>) -> Self {
+        Self { on_update: Some(arg.into()), ..self }
+    }
+
+    /// Choose if the length of a varchar counts characters or bytes
+    ///
+    /// MySQL picks the column character set from this: `utf8mb4` for
+    /// characters and the single-byte `latin1` for bytes. Other
+    /// backends ignore it.
+    pub fn length_semantics(self, arg: LengthSemantics) -> Self {
+        Self { length_semantics: Some(arg), ..self }
+    }
+
+    /// Attach a comment to a table-level constraint or an index
+    ///
+    /// Postgres emits a `COMMENT ON CONSTRAINT` or `COMMENT ON INDEX`
+    /// statement after the table. Other backends drop the comment.
+    pub fn comment<S: Into<String>>(self, arg: S) -> Self {
+        Self { comment: Some(arg.into()), ..self }
+    }
+
+    /// Choose the compression method for large values of this column
+    ///
+    /// Postgres (14+) renders `COMPRESSION <method>`, e.g. `lz4`.
+    /// Other backends ignore it.
+    pub fn compression<S: Into<String>>(self, method: S) -> Self {
+        Self { compression: Some(method.into()), ..self }
+    }
+
+    /// Choose the storage mode for this column, e.g. `EXTERNAL`
+    ///
+    /// Postgres sets it with a separate `ALTER TABLE` statement after
+    /// the column was created. Other backends ignore it.
+    pub fn storage<S: Into<String>>(self, mode: S) -> Self {
+        Self { storage: Some(mode.into()), ..self }
+    }
+
+    /// Choose the access method of an index, e.g. `gin`
+    ///
+    /// Postgres renders `USING <method>`, other backends ignore it.
+    ///
+    /// ```rust
+    /// # use barrel::types;
+    /// let idx = types::index(vec!["tags"]).index_method("gin");
+    /// ```
+    pub fn index_method<S: Into<String>>(self, method: S) -> Self {
+        Self { index_method: Some(method.into()), ..self }
+    }
+
+    /// Choose how the text of this column is compared and sorted
+    ///
+    /// A binary collation makes a unique text column case-sensitive
+    /// on MySQL, e.g. `utf8mb4_bin`. Postgres quotes the name.
+    ///
+    /// ```rust
+    /// # use barrel::types;
+    /// let col = types::varchar(255).unique(true).collation("utf8mb4_bin");
+    /// ```
+    pub fn collation<S: Into<String>>(self, name: S) -> Self {
+        Self { collation: Some(name.into()), ..self }
+    }
+
+    /// Make this an identity column, whose values come from a sequence
+    ///
+    /// Postgres renders `GENERATED <mode> AS IDENTITY`, MySQL can only
+    /// generate values by default, with `AUTO_INCREMENT`. Not supported
+    /// by Sqlite.
+    pub fn identity(self, mode: Identity) -> Self {
+        Self { identity: Some(mode), ..self }
+    }
+
+    /// Choose what happens to this foreign key when the referenced row is deleted
+    ///
+    /// Only used by `foreign` columns, which render `ON DELETE <action>`
+    /// after the reference. MySQL ignores references in column
+    /// definitions, use `foreign_constraint` there instead.
+    pub fn on_delete(self, action: ReferentialAction) -> Self {
+        Self { on_delete: Some(action), ..self }
+    }
+
+    /// Restrict a geometry column to one spatial reference system
+    ///
+    /// MySQL (8+) renders `SRID <id>` after the type, Postgres puts
+    /// it in the PostGIS type modifier. Not supported by Sqlite.
+    ///
+    /// ```rust
+    /// # use barrel::types;
+    /// let col = types::point().srid(4326);
+    /// ```
+    pub fn srid(self, id: u32) -> Self {
+        Self { srid: Some(id), ..self }
+    }
+
+    /// Hide this column from `SELECT *` (only MySQL 8)
+    ///
+    /// The column can still be selected by name. Other backends
+    /// ignore it.
+    pub fn invisible(self, arg: bool) -> Self {
+        Self { invisible: arg, ..self }
+    }
+
+    /// Compute the value of this column from other columns of the row
+    ///
+    /// The expression is raw SQL and rendered as a stored generated
+    /// column, `GENERATED ALWAYS AS (<expr>) STORED`. Generated columns
+    /// can't have a default value, and Sqlite can only create them
+    /// together with their table.
+    pub fn generated<S: Into<String>>(self, expr: S) -> Self {
+        Self { generated: Some(expr.into()), ..self }
+    }
+
+    /// Add this column to a named index shared with other columns
+    ///
+    /// All columns of a table with the same group end up in one index,
+    /// in the order they were added. It's a shorthand for `add_index`.
+    ///
+    /// ```rust
+    /// # use barrel::{types, Migration};
+    /// # let mut m = Migration::new();
+    /// m.create_table("people", |t| {
+    ///     t.add_column("first", types::text().index_group("people_name_idx"));
+    ///     t.add_column("last", types::text().index_group("people_name_idx"));
+    /// });
+    /// ```
+    pub fn index_group<S: Into<String>>(self, name: S) -> Self {
+        Self { index_group: Some(name.into()), ..self }
+    }
+
+    /// Name the index that MySQL creates for a foreign key constraint
+    ///
+    /// MySQL otherwise names the index after the constraint. Other
+    /// backends don't create an index and ignore the name.
+    ///
+    /// This panics if the type isn't a `types::foreign_constraint`.
+    pub fn index_name<S: Into<String>>(self, name: S) -> Self {
+        match self.inner {
+            BaseType::Constraint(ConstraintType::Foreign { schema, table, refs, on_delete, on_update, .. }, columns) => Self {
+                inner: BaseType::Constraint(
+                    ConstraintType::Foreign { schema, table, refs, on_delete, on_update, index: Some(name.into()) },
+                    columns,
+                ),
+                ..self
+            },
+            _ => panic!("Only foreign key constraints have a backing index name!"),
+        }
+    }
+
+    /// Keep a check constraint from being inherited by child tables
+    ///
+    /// This is only supported by Postgres and panics if the type isn't
+    /// a `types::check_constraint`.
+    pub fn no_inherit(self, arg: bool) -> Self {
+        match self.inner {
+            BaseType::Constraint(ConstraintType::Check { expr, .. }, columns) => Self {
+                inner: BaseType::Constraint(ConstraintType::Check { expr, no_inherit: arg }, columns),
+                ..self
+            },
+            _ => panic!("Only check constraints can be kept from being inherited!"),
+        }
+    }
+
     /// Specify a size limit (important or varchar & similar)
     pub fn size(self, arg: usize) -> Self {
         Self { size: Some(arg), ..self }
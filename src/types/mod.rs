@@ -5,5 +5,9 @@ mod defaults;
 mod impls;
 pub use self::builders::*;
 
-pub use self::defaults::WrappedDefault;
-pub use self::impls::{BaseType, Type, WrapVec};
+pub use self::defaults::{
+    array_default, current_time, current_timestamp, expression, nextval, random_uuid, WrappedDefault,
+};
+pub use self::impls::{
+    Affinity, BaseType, ConstraintType, Identity, IndexColumn, LengthSemantics, Nulls, ReferentialAction, Type, WrapVec,
+};
@@ -0,0 +1,41 @@
+//! Views, which are stored queries that can be used like tables
+
+/// Some metadata about a view that was just created
+///
+/// The query of a view is raw SQL and passed through as-is.
+#[derive(Debug, Clone)]
+pub struct ViewMeta {
+    pub name: String,
+    pub select: String,
+    pub or_replace: bool,
+    pub check_option: bool,
+}
+
+impl ViewMeta {
+    /// Create a new viewmeta with default values
+    pub fn new(name: String, select: String) -> Self {
+        Self {
+            name,
+            select,
+            or_replace: false,
+            check_option: false,
+        }
+    }
+
+    /// Replace an existing view with the same name
+    ///
+    /// This is only supported by Postgres and MySQL.
+    pub fn or_replace(&mut self, arg: bool) -> &mut ViewMeta {
+        self.or_replace = arg;
+        self
+    }
+
+    /// Reject writes through the view that it wouldn't show afterwards
+    ///
+    /// This renders `WITH CHECK OPTION` and is only supported by
+    /// Postgres and MySQL.
+    pub fn check_option(&mut self, arg: bool) -> &mut ViewMeta {
+        self.check_option = arg;
+        self
+    }
+}